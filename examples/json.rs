@@ -166,6 +166,14 @@ fn main() {
                     ))
                     .with_color(Color::Yellow),
             ),
+            chumsky::error::SimpleReason::Mismatched { span, delimiter } => report.with_label(
+                Label::new(span.clone())
+                    .with_message(format!(
+                        "Delimiter {} opened here",
+                        delimiter.fg(Color::Yellow)
+                    ))
+                    .with_color(Color::Yellow),
+            ),
             chumsky::error::SimpleReason::Unexpected => report,
             chumsky::error::SimpleReason::Custom(_) => report,
         };
@@ -593,6 +593,34 @@ fn main() {
                             ))
                             .with_color(Color::Red),
                     ),
+                chumsky::error::SimpleReason::Mismatched { span, delimiter } => report
+                    .with_message(format!(
+                        "Mismatched closing delimiter {}",
+                        e.found()
+                            .unwrap_or(&"end of file".to_string())
+                            .fg(Color::Red)
+                    ))
+                    .with_label(
+                        Label::new(span.clone())
+                            .with_message(format!(
+                                "Delimiter {} opened here",
+                                delimiter.fg(Color::Yellow)
+                            ))
+                            .with_color(Color::Yellow),
+                    )
+                    .with_label(
+                        Label::new(e.span())
+                            .with_message(format!(
+                                "Expected {} to close it",
+                                e.expected()
+                                    .filter_map(|expected| expected.as_ref())
+                                    .map(|expected| expected.to_string())
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                                    .fg(Color::Red)
+                            ))
+                            .with_color(Color::Red),
+                    ),
                 chumsky::error::SimpleReason::Unexpected => report
                     .with_message(format!(
                         "{}, expected {}",
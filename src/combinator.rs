@@ -6,6 +6,7 @@
 //! when accessed through their respective methods on [`Parser`].
 
 use super::*;
+use alloc::string::ToString;
 
 /// See [`Parser::ignored`].
 pub type Ignored<P, O> = To<P, O, ()>;
@@ -19,7 +20,7 @@ pub type ThenIgnore<A, B, O, U> = Map<Then<A, B>, fn((O, U)) -> O, (O, U)>;
 /// See [`Parser::or`].
 #[must_use]
 #[derive(Copy, Clone)]
-pub struct Or<A, B>(pub(crate) A, pub(crate) B);
+pub struct Or<A, B>(pub(crate) A, pub(crate) B, pub(crate) Location<'static>);
 
 impl<I: Clone, O, A: Parser<I, O, Error = E>, B: Parser<I, O, Error = E>, E: Error<I>> Parser<I, O>
     for Or<A, B>
@@ -33,6 +34,7 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, B: Parser<I, O, Error = E>, E: Err
         stream: &mut StreamOf<I, E>,
     ) -> PResult<I, O, E> {
         let pre_state = stream.save();
+        let checkpoint = stream.record_checkpoint();
 
         #[allow(deprecated)]
         let a_res = debugger.invoke(&self.0, stream);
@@ -47,6 +49,8 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, B: Parser<I, O, Error = E>, E: Err
             }
         }
 
+        // Keep the records made within the first parser in case it ends up being chosen
+        let a_records = stream.take_records(checkpoint);
         stream.revert(pre_state);
 
         #[allow(deprecated)]
@@ -59,12 +63,16 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, B: Parser<I, O, Error = E>, E: Err
             }
         }
 
+        let b_records = stream.take_records(checkpoint);
+
         #[inline]
         fn choose_between<I: Clone, O, E: Error<I>>(
             a_res: PResult<I, O, E>,
             a_state: usize,
+            a_records: Vec<Record>,
             b_res: PResult<I, O, E>,
             b_state: usize,
+            b_records: Vec<Record>,
             stream: &mut StreamOf<I, E>,
         ) -> PResult<I, O, E> {
             fn zip_with<A, B, R, F: FnOnce(A, B) -> R>(
@@ -130,6 +138,7 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, B: Parser<I, O, Error = E>, E: Err
 
             if is_a {
                 stream.revert(a_state);
+                stream.restore_records(a_records);
                 (
                     a_res.0,
                     a_res.1.map(|(out, alt)| {
@@ -141,6 +150,7 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, B: Parser<I, O, Error = E>, E: Err
                 )
             } else {
                 stream.revert(b_state);
+                stream.restore_records(b_records);
                 (
                     b_res.0,
                     b_res.1.map(|(out, alt)| {
@@ -153,7 +163,15 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, B: Parser<I, O, Error = E>, E: Err
             }
         }
 
-        choose_between(a_res, a_state, b_res, b_state, stream)
+        choose_between(a_res, a_state, a_records, b_res, b_state, b_records, stream)
+    }
+
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::new(
+            "or",
+            Kind::Choice(vec![self.0.describe(describer), self.1.describe(describer)]),
+        )
+        .with_location(self.2)
     }
 
     #[inline]
@@ -191,6 +209,13 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, E: Error<I>> Parser<I, Option<O>>
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::new(
+            "or_not",
+            Kind::Optional(Box::new(self.0.describe(describer))),
+        )
+    }
+
     #[inline]
     fn parse_inner_verbose(
         &self,
@@ -265,6 +290,10 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, E: Error<I>> Parser<I, I> for Not<
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::new("not", Kind::Not(Box::new(self.0.describe(describer))))
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, I, E> {
         #[allow(deprecated)]
@@ -314,6 +343,13 @@ impl<I: Clone, O, U, A: Parser<I, O, Error = E>, B: Parser<I, U, Error = E>, E:
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::new(
+            "then",
+            Kind::Seq(vec![self.0.describe(describer), self.1.describe(describer)]),
+        )
+    }
+
     #[inline]
     fn parse_inner_verbose(
         &self,
@@ -394,6 +430,13 @@ impl<
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::new(
+            "then_with",
+            Kind::ThenWith(Box::new(self.0.describe(describer))),
+        )
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O2, E> {
         #[allow(deprecated)]
@@ -448,6 +491,183 @@ impl<
         (errors, res)
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::new(
+            "delimited_by",
+            Kind::Seq(vec![
+                self.start.describe(describer),
+                self.item.describe(describer),
+                self.end.describe(describer),
+            ]),
+        )
+    }
+
+    #[inline]
+    fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+    #[inline]
+    fn parse_inner_silent(&self, d: &mut Silent, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+}
+
+/// See [`Parser::delimited_by_balanced`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct DelimitedByBalanced<A, I, F, const N: usize> {
+    pub(crate) item: A,
+    pub(crate) start: I,
+    pub(crate) end: I,
+    pub(crate) others: [(I, I); N],
+    pub(crate) fallback: Option<F>,
+    pub(crate) location: Location<'static>,
+}
+
+impl<A, I, F, const N: usize> DelimitedByBalanced<A, I, F, N> {
+    /// Specify other delimiter pairs that may appear nested within the delimiters.
+    ///
+    /// These are only used during recovery (see [`DelimitedByBalanced::with_fallback`]), allowing a closing delimiter
+    /// that appears within, say, a nested `[...]` to be ignored when searching for the matching closing delimiter.
+    pub fn with_others<const M: usize>(
+        self,
+        others: [(I, I); M],
+    ) -> DelimitedByBalanced<A, I, F, M> {
+        DelimitedByBalanced {
+            item: self.item,
+            start: self.start,
+            end: self.end,
+            others,
+            fallback: self.fallback,
+            location: self.location,
+        }
+    }
+
+    /// Recover from errors within the delimiters by skipping to the matching closing delimiter, respecting nesting,
+    /// and generating an output from the span of the delimited section using the given function.
+    ///
+    /// If the end of the input is reached before a matching closing delimiter is found, an unclosed delimiter error is
+    /// emitted and the rest of the input is consumed.
+    pub fn with_fallback<G>(self, fallback: G) -> DelimitedByBalanced<A, I, G, N> {
+        DelimitedByBalanced {
+            item: self.item,
+            start: self.start,
+            end: self.end,
+            others: self.others,
+            fallback: Some(fallback),
+            location: self.location,
+        }
+    }
+}
+
+impl<
+        I: Clone + PartialEq,
+        O,
+        A: Parser<I, O, Error = E>,
+        F: Fn(E::Span) -> O,
+        E: Error<I>,
+        const N: usize,
+    > Parser<I, O> for DelimitedByBalanced<A, I, F, N>
+{
+    type Error = E;
+
+    #[inline]
+    fn parse_inner<D: Debugger>(
+        &self,
+        debugger: &mut D,
+        stream: &mut StreamOf<I, E>,
+    ) -> PResult<I, O, E> {
+        let pre_state = stream.save();
+        let start_span = match stream.next() {
+            (_, span, Some(tok)) if tok == self.start => span,
+            (at, span, found) => {
+                return (
+                    Vec::new(),
+                    Err(Located::at(
+                        at,
+                        E::expected_input_found(span, Some(Some(self.start.clone())), found),
+                    )),
+                )
+            }
+        };
+        let inner_state = stream.save();
+
+        #[allow(deprecated)]
+        let (mut errors, res) = debugger.invoke(&self.item, stream);
+        let err = match res {
+            Ok((out, alt)) => match stream.next() {
+                (_, _, Some(tok)) if tok == self.end => return (errors, Ok((out, alt))),
+                (at, span, None) => Located::at(
+                    at,
+                    E::unclosed_delimiter(
+                        start_span.clone(),
+                        self.start.clone(),
+                        span,
+                        self.end.clone(),
+                        None,
+                    ),
+                )
+                .max(alt),
+                (at, span, found) => Located::at(
+                    at,
+                    E::expected_input_found(span, Some(Some(self.end.clone())), found),
+                )
+                .max(alt),
+            },
+            Err(err) => err,
+        };
+
+        match &self.fallback {
+            Some(fallback) if stream.can_recover() => {
+                stream.revert(inner_state);
+                let err = if recovery::skip_to_close(stream, &self.start, &self.end, &self.others) {
+                    err
+                } else {
+                    let (at, span, _) = stream.next();
+                    let unclosed = Located::at(
+                        at,
+                        E::unclosed_delimiter(
+                            start_span,
+                            self.start.clone(),
+                            span,
+                            self.end.clone(),
+                            None,
+                        ),
+                    );
+                    if err.at < at {
+                        errors.push(err);
+                        unclosed
+                    } else {
+                        unclosed.max(err)
+                    }
+                };
+                errors.push(err);
+                stream.recovered(
+                    pre_state,
+                    &mut errors,
+                    "delimited_by_balanced",
+                    self.location,
+                );
+                (errors, Ok((fallback(stream.span_since(pre_state)), None)))
+            }
+            _ => (errors, Err(err)),
+        }
+    }
+
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::new(
+            "delimited_by_balanced",
+            Kind::Seq(vec![
+                Node::new("just", Kind::Just(vec![self.start.clone()])),
+                self.item.describe(describer),
+                Node::new("just", Kind::Just(vec![self.end.clone()])),
+            ]),
+        )
+        .with_location(self.location)
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
         #[allow(deprecated)]
@@ -463,9 +683,15 @@ impl<
 /// See [`Parser::repeated`].
 #[must_use]
 #[derive(Copy, Clone)]
-pub struct Repeated<A>(pub(crate) A, pub(crate) usize, pub(crate) Option<usize>);
+pub struct Repeated<A, R = ()>(
+    pub(crate) A,
+    pub(crate) usize,
+    pub(crate) Option<usize>,
+    pub(crate) R,
+    pub(crate) Location<'static>,
+);
 
-impl<A> Repeated<A> {
+impl<A, R> Repeated<A, R> {
     /// Require that the pattern appear at least a minimum number of times.
     pub fn at_least(mut self, min: usize) -> Self {
         self.1 = min;
@@ -519,9 +745,65 @@ impl<A> Repeated<A> {
         self.2 = Some(n);
         self
     }
+
+    /// Recover from items that fail to parse by skipping inputs until the item can be parsed again, or until one of
+    /// the given terminators is found, recording an error and then continuing to parse further items.
+    ///
+    /// The terminators are not consumed. If the item fails at a terminator (or at the end of the input), no recovery
+    /// occurs and repetition ends as usual.
+    ///
+    /// See [`Repeated::recover_items_with`] if you'd like to insert a placeholder output for the failed item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let block = text::ident::<_, Simple<char>>()
+    ///     .then_ignore(just(';'))
+    ///     .padded()
+    ///     .repeated()
+    ///     .recover_items(['}'])
+    ///     .delimited_by(just('{'), just('}'));
+    ///
+    /// let (stmts, errors) = block.parse_recovery("{ a; 42; b; }");
+    /// assert_eq!(stmts, Some(vec!["a".to_string(), "b".to_string()]));
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    #[track_caller]
+    pub fn recover_items<I, const N: usize>(
+        self,
+        terminators: [I; N],
+    ) -> Repeated<A, RecoverItems<I, (), N>> {
+        Repeated(
+            self.0,
+            self.1,
+            self.2,
+            RecoverItems(terminators, (), *Location::caller()),
+            self.4,
+        )
+    }
+
+    /// Like [`Repeated::recover_items`], but inserts a placeholder output, generated from the span of the skipped
+    /// inputs, in place of each failed item.
+    #[track_caller]
+    pub fn recover_items_with<I, F, const N: usize>(
+        self,
+        terminators: [I; N],
+        placeholder: F,
+    ) -> Repeated<A, RecoverItems<I, F, N>> {
+        Repeated(
+            self.0,
+            self.1,
+            self.2,
+            RecoverItems(terminators, placeholder, *Location::caller()),
+            self.4,
+        )
+    }
 }
 
-impl<I: Clone, O, A: Parser<I, O, Error = E>, E: Error<I>> Parser<I, Vec<O>> for Repeated<A> {
+impl<I: Clone, O, A: Parser<I, O, Error = E>, R: ItemRecovery<I, O, E>, E: Error<I>>
+    Parser<I, Vec<O>> for Repeated<A, R>
+{
     type Error = E;
 
     #[inline]
@@ -533,59 +815,96 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, E: Error<I>> Parser<I, Vec<O>> for
         let mut errors = Vec::new();
         let mut outputs = Vec::new();
         let mut alt = None;
-        let mut old_offset = None;
 
         loop {
             if self.2.map_or(false, |max| outputs.len() >= max) {
                 break (errors, Ok((outputs, alt)));
             }
 
-            if let ControlFlow::Break(b) = stream.attempt(|stream| match { #[allow(deprecated)] debugger.invoke(&self.0, stream) } {
-                (mut a_errors, Ok((a_out, a_alt))) => {
-                    errors.append(&mut a_errors);
-                    alt = merge_alts(alt.take(), a_alt);
-                    outputs.push(a_out);
-
-                    if old_offset == Some(stream.offset()) {
-                        panic!("Repeated parser iteration succeeded but consumed no inputs (i.e: continuing \
-                            iteration would likely lead to an infinite loop, if the parser is pure). This is \
-                            likely indicative of a parser bug. Consider using a more specific error recovery \
-                            strategy.");
-                    } else {
-                        old_offset = Some(stream.offset());
+            let start = stream.save();
+            if let ControlFlow::Break(b) = stream.attempt(|stream| {
+                match {
+                    #[allow(deprecated)]
+                    debugger.invoke(&self.0, stream)
+                } {
+                    (mut a_errors, Ok((a_out, a_alt))) => {
+                        errors.append(&mut a_errors);
+                        alt = merge_alts(alt.take(), a_alt);
+                        outputs.push(a_out);
+                        (true, ControlFlow::Continue(()))
                     }
-
-                    (true, ControlFlow::Continue(()))
-                },
-                (mut a_errors, Err(a_err)) if outputs.len() < self.1 => {
-                    errors.append(&mut a_errors);
-                    (true, ControlFlow::Break((
-                        core::mem::take(&mut errors),
-                        Err(a_err),
-                    )))
-                },
-                (a_errors, Err(a_err)) => {
-                    // Find furthest alternative error
-                    // TODO: Handle multiple alternative errors
-                    // TODO: Should we really be taking *all* of these into consideration?
-                    let alt = merge_alts(
-                        alt.take(),
-                        merge_alts(
-                            Some(a_err),
-                            a_errors.into_iter().next(),
-                        ),
-                    );
-                    (false, ControlFlow::Break((
-                        core::mem::take(&mut errors),
-                        Ok((core::mem::take(&mut outputs), alt)),
-                    )))
-                },
+                    (mut a_errors, Err(a_err)) => {
+                        a_errors.push(a_err);
+                        let recovered =
+                            self.3
+                                .recover_item(start, &mut a_errors, stream, &mut |stream| {
+                                    stream.attempt(|stream| {
+                                        #[allow(deprecated)]
+                                        let res = debugger.invoke(&self.0, stream).1;
+                                        (false, res.is_ok())
+                                    })
+                                });
+                        match recovered {
+                            Some(placeholder) => {
+                                errors.append(&mut a_errors);
+                                outputs.extend(placeholder);
+                                (true, ControlFlow::Continue(()))
+                            }
+                            None if outputs.len() < self.1 => {
+                                let a_err = a_errors.pop().unwrap();
+                                errors.append(&mut a_errors);
+                                (
+                                    true,
+                                    ControlFlow::Break((core::mem::take(&mut errors), Err(a_err))),
+                                )
+                            }
+                            None => {
+                                let a_err = a_errors.pop().unwrap();
+                                // Find furthest alternative error
+                                // TODO: Handle multiple alternative errors
+                                // TODO: Should we really be taking *all* of these into consideration?
+                                let alt = merge_alts(
+                                    alt.take(),
+                                    merge_alts(Some(a_err), a_errors.into_iter().next()),
+                                );
+                                (
+                                    false,
+                                    ControlFlow::Break((
+                                        core::mem::take(&mut errors),
+                                        Ok((core::mem::take(&mut outputs), alt)),
+                                    )),
+                                )
+                            }
+                        }
+                    }
+                }
             }) {
                 break b;
             }
+
+            // Continuing would never terminate if the parser is pure
+            if stream.offset() == start && self.2.iter().all(|max| outputs.len() < *max) {
+                errors.extend(stream.stalled_loop(self.4));
+                break (errors, Ok((outputs, alt)));
+            }
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::new(
+            "repeated",
+            Kind::Repeat {
+                item: Box::new(self.0.describe(describer)),
+                separator: None,
+                at_least: self.1,
+                at_most: self.2,
+                allow_leading: false,
+                allow_trailing: false,
+            },
+        )
+        .with_location(self.4)
+    }
+
     #[inline]
     fn parse_inner_verbose(
         &self,
@@ -604,17 +923,19 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, E: Error<I>> Parser<I, Vec<O>> for
 
 /// See [`Parser::separated_by`].
 #[must_use]
-pub struct SeparatedBy<A, B, U> {
+pub struct SeparatedBy<A, B, U, R = ()> {
     pub(crate) item: A,
     pub(crate) delimiter: B,
     pub(crate) at_least: usize,
     pub(crate) at_most: Option<usize>,
     pub(crate) allow_leading: bool,
     pub(crate) allow_trailing: bool,
+    pub(crate) recovery: R,
+    pub(crate) location: Location<'static>,
     pub(crate) phantom: PhantomData<U>,
 }
 
-impl<A, B, U> SeparatedBy<A, B, U> {
+impl<A, B, U, R> SeparatedBy<A, B, U, R> {
     /// Allow a leading separator to appear before the first item.
     ///
     /// Note that even if no items are parsed, a leading separator *is* permitted.
@@ -731,10 +1052,87 @@ impl<A, B, U> SeparatedBy<A, B, U> {
         self.at_most = Some(n);
         self
     }
+
+    /// Recover from items that fail to parse by skipping inputs until the next separator or one of the given
+    /// terminators, recording an error and then continuing to parse further items.
+    ///
+    /// The terminators are not consumed. If an item fails at a terminator (or at the end of the input), no recovery
+    /// occurs and the list ends as usual.
+    ///
+    /// See [`SeparatedBy::recover_items_with`] if you'd like to insert a placeholder output for the failed item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let args = text::int::<_, Simple<char>>(10)
+    ///     .padded()
+    ///     .separated_by(just(','))
+    ///     .recover_items([')'])
+    ///     .delimited_by(just('('), just(')'));
+    ///
+    /// let (args, errors) = args.parse_recovery("(1, +2, 3)");
+    /// assert_eq!(args, Some(vec!["1".to_string(), "3".to_string()]));
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    #[track_caller]
+    pub fn recover_items<I, const N: usize>(
+        self,
+        terminators: [I; N],
+    ) -> SeparatedBy<A, B, U, RecoverItems<I, (), N>> {
+        self.with_recovery(RecoverItems(terminators, (), *Location::caller()))
+    }
+
+    /// Like [`SeparatedBy::recover_items`], but inserts a placeholder output, generated from the span of the skipped
+    /// inputs, in place of each failed item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Debug, PartialEq)]
+    /// enum Arg {
+    ///     Num(String),
+    ///     Error,
+    /// }
+    ///
+    /// let args = text::int::<_, Simple<char>>(10)
+    ///     .map(Arg::Num)
+    ///     .padded()
+    ///     .separated_by(just(','))
+    ///     .recover_items_with([')'], |_| Arg::Error)
+    ///     .delimited_by(just('('), just(')'));
+    ///
+    /// let (args, errors) = args.parse_recovery("(1, ?, 3)");
+    /// assert_eq!(args, Some(vec![Arg::Num("1".to_string()), Arg::Error, Arg::Num("3".to_string())]));
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    #[track_caller]
+    pub fn recover_items_with<I, F, const N: usize>(
+        self,
+        terminators: [I; N],
+        placeholder: F,
+    ) -> SeparatedBy<A, B, U, RecoverItems<I, F, N>> {
+        self.with_recovery(RecoverItems(terminators, placeholder, *Location::caller()))
+    }
+
+    fn with_recovery<S>(self, recovery: S) -> SeparatedBy<A, B, U, S> {
+        SeparatedBy {
+            item: self.item,
+            delimiter: self.delimiter,
+            at_least: self.at_least,
+            at_most: self.at_most,
+            allow_leading: self.allow_leading,
+            allow_trailing: self.allow_trailing,
+            recovery,
+            location: self.location,
+            phantom: PhantomData,
+        }
+    }
 }
 
-impl<A: Copy, B: Copy, U> Copy for SeparatedBy<A, B, U> {}
-impl<A: Clone, B: Clone, U> Clone for SeparatedBy<A, B, U> {
+impl<A: Copy, B: Copy, U, R: Copy> Copy for SeparatedBy<A, B, U, R> {}
+impl<A: Clone, B: Clone, U, R: Clone> Clone for SeparatedBy<A, B, U, R> {
     fn clone(&self) -> Self {
         Self {
             item: self.item.clone(),
@@ -743,13 +1141,22 @@ impl<A: Clone, B: Clone, U> Clone for SeparatedBy<A, B, U> {
             at_most: self.at_most,
             allow_leading: self.allow_leading,
             allow_trailing: self.allow_trailing,
+            recovery: self.recovery.clone(),
+            location: self.location,
             phantom: PhantomData,
         }
     }
 }
 
-impl<I: Clone, O, U, A: Parser<I, O, Error = E>, B: Parser<I, U, Error = E>, E: Error<I>>
-    Parser<I, Vec<O>> for SeparatedBy<A, B, U>
+impl<
+        I: Clone,
+        O,
+        U,
+        A: Parser<I, O, Error = E>,
+        B: Parser<I, U, Error = E>,
+        R: ItemRecovery<I, O, E>,
+        E: Error<I>,
+    > Parser<I, Vec<O>> for SeparatedBy<A, B, U, R>
 {
     type Error = E;
 
@@ -768,7 +1175,7 @@ impl<I: Clone, O, U, A: Parser<I, O, Error = E>, B: Parser<I, U, Error = E>, E:
             );
         }
 
-        enum State<I, E> {
+        enum State<I, E: Error<I>> {
             Terminated(Located<I, E>),
             Continue,
         }
@@ -789,14 +1196,27 @@ impl<I: Clone, O, U, A: Parser<I, O, Error = E>, B: Parser<I, U, Error = E>, E:
             }
         }
 
-        fn parse<O, A: Parser<I, O, Error = E>, I: Clone, E: Error<I>, D: Debugger>(
+        #[allow(clippy::too_many_arguments)]
+        fn parse<
+            O,
+            U,
+            A: Parser<I, O, Error = E>,
+            B: Parser<I, U, Error = E>,
+            R: ItemRecovery<I, O, E>,
+            I: Clone,
+            E: Error<I>,
+            D: Debugger,
+        >(
             item: &A,
+            delimiter: &B,
+            recovery: &R,
             stream: &mut StreamOf<I, E>,
             debugger: &mut D,
             outputs: &mut Vec<O>,
             errors: &mut Vec<Located<I, E>>,
             alt: Option<Located<I, E>>,
         ) -> (State<I, E>, Option<Located<I, E>>) {
+            let start = stream.save();
             match stream.try_parse(|stream| {
                 #[allow(deprecated)]
                 debugger.invoke(item, stream)
@@ -807,8 +1227,25 @@ impl<I: Clone, O, U, A: Parser<I, O, Error = E>, B: Parser<I, U, Error = E>, E:
                     (State::Continue, merge_alts(alt, i_alt))
                 }
                 (mut i_errors, Err(i_err)) => {
-                    errors.append(&mut i_errors);
-                    (State::Terminated(i_err), alt)
+                    i_errors.push(i_err);
+                    match recovery.recover_item(start, &mut i_errors, stream, &mut |stream| {
+                        stream.attempt(|stream| {
+                            #[allow(deprecated)]
+                            let res = debugger.invoke(delimiter, stream).1;
+                            (false, res.is_ok())
+                        })
+                    }) {
+                        Some(placeholder) => {
+                            errors.append(&mut i_errors);
+                            outputs.extend(placeholder);
+                            (State::Continue, alt)
+                        }
+                        None => {
+                            let i_err = i_errors.pop().unwrap();
+                            errors.append(&mut i_errors);
+                            (State::Terminated(i_err), alt)
+                        }
+                    }
                 }
             }
         }
@@ -821,8 +1258,16 @@ impl<I: Clone, O, U, A: Parser<I, O, Error = E>, B: Parser<I, U, Error = E>, E:
             alt = parse_or_not(&self.delimiter, stream, debugger, alt);
         }
 
-        let (mut state, mut alt) =
-            parse(&self.item, stream, debugger, &mut outputs, &mut errors, alt);
+        let (mut state, mut alt) = parse(
+            &self.item,
+            &self.delimiter,
+            &self.recovery,
+            stream,
+            debugger,
+            &mut outputs,
+            &mut errors,
+            alt,
+        );
 
         let mut offset = stream.save();
         let error: Option<Located<I, E>>;
@@ -849,10 +1294,28 @@ impl<I: Clone, O, U, A: Parser<I, O, Error = E>, B: Parser<I, U, Error = E>, E:
                     errors.append(&mut d_errors);
                     alt = merge_alts(alt, d_alt);
 
-                    let (i_state, i_alt) =
-                        parse(&self.item, stream, debugger, &mut outputs, &mut errors, alt);
+                    let (i_state, i_alt) = parse(
+                        &self.item,
+                        &self.delimiter,
+                        &self.recovery,
+                        stream,
+                        debugger,
+                        &mut outputs,
+                        &mut errors,
+                        alt,
+                    );
                     state = i_state;
                     alt = i_alt;
+
+                    // Continuing would never terminate if the delimiter and item are pure
+                    if matches!(state, State::Continue)
+                        && stream.offset() == offset
+                        && self.at_most.iter().all(|at_most| outputs.len() < *at_most)
+                    {
+                        errors.extend(stream.stalled_loop(self.location));
+                        error = None;
+                        break;
+                    }
                 }
                 (mut d_errors, Err(d_err)) => {
                     errors.append(&mut d_errors);
@@ -877,6 +1340,21 @@ impl<I: Clone, O, U, A: Parser<I, O, Error = E>, B: Parser<I, U, Error = E>, E:
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::new(
+            "separated_by",
+            Kind::Repeat {
+                item: Box::new(self.item.describe(describer)),
+                separator: Some(Box::new(self.delimiter.describe(describer))),
+                at_least: self.at_least,
+                at_most: self.at_most,
+                allow_leading: self.allow_leading,
+                allow_trailing: self.allow_trailing,
+            },
+        )
+        .with_location(self.location)
+    }
+
     #[inline]
     fn parse_inner_verbose(
         &self,
@@ -927,6 +1405,15 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, E: Error<I>> Parser<I, O> for Debu
         )
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        let label = self.1.to_string();
+        let node = self.0.describe(describer);
+        describer.name_rule(&node, &label);
+        Node::wrap("debug", node)
+            .with_label(label)
+            .with_location(self.2)
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
         #[allow(deprecated)]
@@ -967,6 +1454,10 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, U, F: Fn(O) -> U, E: Error<I>> Par
         (errors, res.map(|(out, alt)| ((&self.1)(out), alt)))
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("map", self.0.describe(describer))
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, U, E> {
         #[allow(deprecated)]
@@ -1011,6 +1502,193 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, U, F: Fn(O, E::Span) -> U, E: Erro
         )
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("map_with_span", self.0.describe(describer))
+    }
+
+    #[inline]
+    fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, U, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+    #[inline]
+    fn parse_inner_silent(&self, d: &mut Silent, s: &mut StreamOf<I, E>) -> PResult<I, U, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+}
+
+/// See [`Parser::map_recovered`].
+#[must_use]
+pub struct MapRecovered<A, F, O>(pub(crate) A, pub(crate) F, pub(crate) PhantomData<O>);
+
+impl<A: Copy, F: Copy, O> Copy for MapRecovered<A, F, O> {}
+impl<A: Clone, F: Clone, O> Clone for MapRecovered<A, F, O> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), self.1.clone(), PhantomData)
+    }
+}
+
+impl<I: Clone, O, A: Parser<I, O, Error = E>, U, F: Fn(O, bool, E::Span) -> U, E: Error<I>>
+    Parser<I, U> for MapRecovered<A, F, O>
+{
+    type Error = E;
+
+    #[inline]
+    fn parse_inner<D: Debugger>(
+        &self,
+        debugger: &mut D,
+        stream: &mut StreamOf<I, E>,
+    ) -> PResult<I, U, E> {
+        let start = stream.save();
+        #[allow(deprecated)]
+        let (errors, res) = debugger.invoke(&self.0, stream);
+
+        // Errors that were recovered from belong to a region, unlike those emitted by `Parser::validate`
+        let recovered = errors.iter().any(|e| e.region.is_some());
+        (
+            errors,
+            res.map(|(out, alt)| ((self.1)(out, recovered, stream.span_since(start)), alt)),
+        )
+    }
+
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("map_recovered", self.0.describe(describer))
+    }
+
+    #[inline]
+    fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, U, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+    #[inline]
+    fn parse_inner_silent(&self, d: &mut Silent, s: &mut StreamOf<I, E>) -> PResult<I, U, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+}
+
+/// See [`Parser::record_trivia`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct RecordTrivia<A>(pub(crate) A);
+
+impl<I: Clone, O, A: Parser<I, O, Error = E>, E: Error<I>> Parser<I, O> for RecordTrivia<A> {
+    type Error = E;
+
+    #[inline]
+    fn parse_inner<D: Debugger>(
+        &self,
+        debugger: &mut D,
+        stream: &mut StreamOf<I, E>,
+    ) -> PResult<I, O, E> {
+        let start = stream.save();
+        #[allow(deprecated)]
+        let (errors, res) = debugger.invoke(&self.0, stream);
+        if res.is_ok() && stream.offset() > start {
+            stream.record(RecordKind::Trivia, start);
+        }
+        (errors, res)
+    }
+
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("record_trivia", self.0.describe(describer))
+    }
+
+    #[inline]
+    fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+    #[inline]
+    fn parse_inner_silent(&self, d: &mut Silent, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+}
+
+/// See [`Parser::highlighted`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct Highlighted<A>(pub(crate) A, pub(crate) HighlightClass);
+
+impl<I: Clone, O, A: Parser<I, O, Error = E>, E: Error<I>> Parser<I, O> for Highlighted<A> {
+    type Error = E;
+
+    #[inline]
+    fn parse_inner<D: Debugger>(
+        &self,
+        debugger: &mut D,
+        stream: &mut StreamOf<I, E>,
+    ) -> PResult<I, O, E> {
+        let start = stream.save();
+        #[allow(deprecated)]
+        let (errors, res) = debugger.invoke(&self.0, stream);
+        if res.is_ok() && stream.state.config.highlight {
+            stream.record(RecordKind::Highlight(self.1), start);
+        }
+        (errors, res)
+    }
+
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("highlighted", self.0.describe(describer))
+    }
+
+    #[inline]
+    fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+    #[inline]
+    fn parse_inner_silent(&self, d: &mut Silent, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+}
+
+/// See [`Parser::map_with_trivia`].
+#[must_use]
+pub struct MapWithTrivia<A, F, O>(pub(crate) A, pub(crate) F, pub(crate) PhantomData<O>);
+
+impl<A: Copy, F: Copy, O> Copy for MapWithTrivia<A, F, O> {}
+impl<A: Clone, F: Clone, O> Clone for MapWithTrivia<A, F, O> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), self.1.clone(), PhantomData)
+    }
+}
+
+impl<
+        I: Clone,
+        O,
+        A: Parser<I, O, Error = E>,
+        U,
+        F: Fn(O, Trivia<I, E::Span>) -> U,
+        E: Error<I>,
+    > Parser<I, U> for MapWithTrivia<A, F, O>
+{
+    type Error = E;
+
+    #[inline]
+    fn parse_inner<D: Debugger>(
+        &self,
+        debugger: &mut D,
+        stream: &mut StreamOf<I, E>,
+    ) -> PResult<I, U, E> {
+        let start = stream.save();
+        let checkpoint = stream.record_checkpoint();
+        #[allow(deprecated)]
+        let (errors, res) = debugger.invoke(&self.0, stream);
+
+        (
+            errors,
+            res.map(|(out, alt)| ((self.1)(out, stream.trivia(checkpoint, start)), alt)),
+        )
+    }
+
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("map_with_trivia", self.0.describe(describer))
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, U, E> {
         #[allow(deprecated)]
@@ -1068,6 +1746,10 @@ impl<
         (errors, res)
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("validate", self.0.describe(describer))
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, U, E> {
         #[allow(deprecated)]
@@ -1115,6 +1797,10 @@ impl<
         )
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("foldl", self.0.describe(describer))
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
         #[allow(deprecated)]
@@ -1164,6 +1850,10 @@ where
         )
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("foldr", self.0.describe(describer))
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, U, E> {
         #[allow(deprecated)]
@@ -1194,7 +1884,7 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, F: Fn(E) -> E, E: Error<I>> Parser
     ) -> PResult<I, O, E> {
         #[allow(deprecated)]
         let (errors, res) = debugger.invoke(&self.0, stream);
-        let mapper = |e: Located<I, E>| e.map(&self.1);
+        let mapper = |e: Located<I, E>| e.map_error(&self.1);
         (
             errors, //errors.into_iter().map(mapper).collect(),
             res /*.map(|(out, alt)| (out, alt.map(mapper)))*/
@@ -1202,6 +1892,10 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, F: Fn(E) -> E, E: Error<I>> Parser
         )
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("map_err", self.0.describe(describer))
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
         #[allow(deprecated)]
@@ -1235,7 +1929,7 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, F: Fn(E, E::Span) -> E, E: Error<I
         let (errors, res) = debugger.invoke(&self.0, stream);
         let mapper = |e: Located<I, E>| {
             let at = e.at;
-            e.map(|e| {
+            e.map_error(|e| {
                 let span = stream.attempt(|stream| {
                     stream.revert(at);
                     (false, stream.span_since(start))
@@ -1246,6 +1940,10 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, F: Fn(E, E::Span) -> E, E: Error<I
         (errors, res.map_err(mapper))
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("map_err_with_span", self.0.describe(describer))
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
         #[allow(deprecated)]
@@ -1299,6 +1997,13 @@ impl<
         (errors, res)
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::new(
+            "try_map",
+            Kind::TryMap(Box::new(self.0.describe(describer))),
+        )
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, U, E> {
         #[allow(deprecated)]
@@ -1336,6 +2041,8 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, F: Fn(E) -> Result<O, E>, E: Error
                 Err(e) => Err(Located {
                     at: err.at,
                     error: e,
+                    region: err.region,
+                    contexts: err.contexts,
                     phantom: PhantomData,
                 }),
                 Ok(out) => Ok((out, None)),
@@ -1345,6 +2052,10 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, F: Fn(E) -> Result<O, E>, E: Error
         (errors, res)
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("or_else", self.0.describe(describer))
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
         #[allow(deprecated)]
@@ -1374,25 +2085,115 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, L: Into<E::Label> + Clone, E: Erro
         stream: &mut StreamOf<I, E>,
     ) -> PResult<I, O, E> {
         // let pre_state = stream.save();
+        let start = stream.save();
         #[allow(deprecated)]
         let (errors, res) = debugger.invoke(&self.0, stream);
+        if res.is_ok() && stream.state.config.cst {
+            stream.record(RecordKind::Node(cst::site(self)), start);
+        }
+        let completing = stream.state.completing;
+        let label = |e: Located<I, E>| {
+            let mut e = e.map_error(|e| e.with_label(self.1.clone().into()));
+            if completing {
+                e.contexts.push(self.1.clone().into());
+            }
+            e
+        };
         let res = res.map_err(|e| {
             /* TODO: Not this? */
             /*if e.at > pre_state
             {*/
             // Only add the label if we committed to this pattern somewhat
-            e.map(|e| e.with_label(self.1.clone().into()))
+            label(e)
             /*} else {
                 e
             }*/
         });
-        (
-            errors
-                .into_iter()
-                .map(|e| e.map(|e| e.with_label(self.1.clone().into())))
-                .collect(),
-            res,
-        )
+        // Only completions need to know the contexts of alternative errors
+        let res = match res {
+            Ok((out, Some(mut alt))) if completing => {
+                alt.contexts.push(self.1.clone().into());
+                Ok((out, Some(alt)))
+            }
+            res => res,
+        };
+        (errors.into_iter().map(label).collect(), res)
+    }
+
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        describer.site(self, || self.1.clone().into());
+        let label = describer.label(self.1.clone().into());
+        let node = self.0.describe(describer);
+        describer.name_rule(&node, &label);
+        Node::wrap("labelled", node).with_label(label)
+    }
+
+    #[inline]
+    fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+    #[inline]
+    fn parse_inner_silent(&self, d: &mut Silent, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+}
+
+/// See [`Parser::memoized`].
+#[must_use]
+pub struct Memoized<A, O>(pub(crate) A, pub(crate) PhantomData<O>);
+
+impl<A: Copy, O> Copy for Memoized<A, O> {}
+impl<A: Clone, O> Clone for Memoized<A, O> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<I, O, A, E> Parser<I, O> for Memoized<A, O>
+where
+    I: Clone,
+    O: Clone + Shift + Send + Sync + 'static,
+    A: Parser<I, O, Error = E>,
+    E: Error<I> + Clone + Shift + Send + Sync + 'static,
+{
+    type Error = E;
+
+    #[inline]
+    fn parse_inner<D: Debugger>(
+        &self,
+        debugger: &mut D,
+        stream: &mut StreamOf<I, E>,
+    ) -> PResult<I, O, E> {
+        // Completions need the contexts of errors, which are not remembered
+        if !stream.state.config.memoize || stream.state.completing {
+            #[allow(deprecated)]
+            return debugger.invoke(&self.0, stream);
+        }
+
+        let site = cst::site(self);
+        if let Some((out, alt)) = stream.reuse(site) {
+            return (Vec::new(), Ok((out, alt)));
+        }
+
+        let checkpoint = stream.memo_checkpoint();
+        #[allow(deprecated)]
+        let (errors, res) = debugger.invoke(&self.0, stream);
+        let output = match (&res, errors.is_empty()) {
+            (Ok((out, alt)), true) => Some(move || {
+                let alt = alt.as_ref().map(|alt| (alt.at, alt.error.clone()));
+                Arc::new((out.clone(), alt)) as Arc<dyn core::any::Any + Send + Sync>
+            }),
+            _ => None,
+        };
+        stream.memoize(checkpoint, site, output);
+        (errors, res)
+    }
+
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        describer.memoized(self);
+        self.0.describe(describer)
     }
 
     #[inline]
@@ -1431,6 +2232,10 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, U: Clone, E: Error<I>> Parser<I, U
         debugger.invoke(&(&self.0).map(|_| self.1.clone()), stream)
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("to", self.0.describe(describer))
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, U, E> {
         #[allow(deprecated)]
@@ -1475,6 +2280,13 @@ where
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::new(
+            "rewind",
+            Kind::Lookahead(Box::new(self.0.describe(describer))),
+        )
+    }
+
     fn parse_inner_verbose(
         &self,
         d: &mut Verbose,
@@ -1539,6 +2351,10 @@ impl<I: Clone, O, A: Parser<I, Result<O, U>, Error = E>, U: fmt::Debug, E: Error
         )
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("unwrapped", self.1.describe(describer)).with_location(*self.0)
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
         #[allow(deprecated)]
@@ -1571,6 +2387,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn delimited_by_balanced_unclosed() {
+        let parser = just::<_, _, Simple<char>>('a')
+            .repeated()
+            .delimited_by_balanced('(', ')');
+
+        assert_eq!(parser.parse("(aa)"), Ok(vec!['a', 'a']));
+
+        let errors = parser.parse("(aa").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span(), 3..3);
+        assert_eq!(
+            errors[0].reason(),
+            &error::SimpleReason::Unclosed {
+                span: 0..1,
+                delimiter: '('
+            }
+        );
+    }
+
+    #[test]
+    fn delimited_by_balanced_recovery() {
+        let parser = just::<_, _, Simple<char>>('a')
+            .repeated()
+            .delimited_by_balanced('(', ')')
+            .with_others([('[', ']')])
+            .with_fallback(|_| Vec::new())
+            .repeated()
+            .then_ignore(end());
+
+        let (out, errors) = parser.parse_recovery("(a)(a[)](a)a)(a)");
+        assert_eq!(out, Some(vec![vec!['a'], vec![], vec!['a']]));
+        assert_eq!(errors.len(), 1);
+
+        let (out, errors) = parser.parse_recovery("(a)(a(a)");
+        assert_eq!(out, Some(vec![vec!['a'], vec![]]));
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[1].reason(),
+            error::SimpleReason::Unclosed { span, .. } if span == &(3..4),
+        ));
+    }
+
     #[test]
     fn separated_by_at_least() {
         let parser = just::<_, _, Simple<char>>('-')
@@ -1628,4 +2487,76 @@ mod tests {
             .chain(just(','));
         assert_eq!(parser.parse("-,-,-,"), Ok(vec!['-', '-', '-', ',']))
     }
+
+    #[test]
+    fn map_recovered() {
+        let parser = text::int::<_, Simple<char>>(10)
+            .validate(|digits: String, span, emit| {
+                if digits.len() > 3 {
+                    emit(Simple::custom(span, "too long"));
+                }
+                digits
+            })
+            .delimited_by(just('['), just(']'))
+            .recover_with(nested_delimiters('[', ']', [], |_| String::new()))
+            .map_recovered(|digits, recovered, _| (digits, recovered));
+
+        assert_eq!(parser.parse("[123]"), Ok(("123".to_string(), false)));
+
+        // Validation errors don't mean that the output was produced through recovery
+        let (output, errors) = parser.parse_recovery("[12345]");
+        assert_eq!(output, Some(("12345".to_string(), false)));
+        assert_eq!(errors.len(), 1);
+
+        let (output, errors) = parser.parse_recovery("[1+2]");
+        assert_eq!(output, Some((String::new(), true)));
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn or_trace_backtracks() {
+        use crate::debug::{TraceEvent, TraceOutcome};
+
+        let parser = just::<_, _, Simple<char>>("ab")
+            .or(just("ac"))
+            .debug("ab or ac");
+        let output = parser.parse_recovery_with("ac", crate::ParseConfig::new().trace());
+        assert_eq!(output.output(), Some(&"ac"));
+
+        let trace = output.trace().unwrap();
+        let node = match &trace.events()[0] {
+            TraceEvent::Invoke(node) => node,
+            _ => panic!("expected an invocation"),
+        };
+        assert_eq!(node.name(), "Debug");
+        assert_eq!(node.label(), Some("ab or ac"));
+        assert_eq!(node.location().unwrap().file(), file!());
+
+        let or = match &node.events()[0] {
+            TraceEvent::Invoke(or) => or,
+            _ => panic!("expected an invocation"),
+        };
+        assert_eq!(or.name(), "Or");
+        match or.events() {
+            [TraceEvent::Invoke(a), TraceEvent::Backtrack { from: 2, to: 0 }, TraceEvent::Invoke(b)] =>
+            {
+                assert_eq!(a.outcome(), TraceOutcome::Failure);
+                assert_eq!(a.errors().len(), 1);
+                assert_eq!(a.errors()[0].at(), 1);
+                let description = a.errors()[0].description();
+                assert_eq!(description.message.as_deref(), Some("found 'c'"));
+                assert_eq!(description.expected, vec![Some("'b'".to_string())]);
+                assert_eq!(b.outcome(), TraceOutcome::Success);
+                assert_eq!(b.offsets(), 0..2);
+            }
+            events => panic!("unexpected events: {:?}", events),
+        }
+        assert!(trace.to_string().contains("backtracked from 2 to 0"));
+        assert!(trace
+            .to_string()
+            .contains("(error at 1: found 'c', expected 'b')"));
+        assert!(trace.to_json().contains(
+            "\"errors\":[{\"at\":1,\"message\":\"found 'c'\",\"expected\":[\"'b'\"],\"label\":null}]"
+        ));
+    }
 }
@@ -0,0 +1,979 @@
+//! Configuration for individual parses, and the outputs that they produce.
+//!
+//! *“The ships hung in the sky in much the same way that bricks don’t.”*
+//!
+//! [`ParseConfig`] allows you to control how errors are collected and reported by [`Parser::parse_recovery_with`],
+//! producing a [`ParseOutput`].
+
+use super::*;
+use alloc::collections::BTreeMap;
+use core::sync::atomic::AtomicBool;
+
+/// Configuration for a single parse. See [`Parser::parse_recovery_with`].
+///
+/// The default configuration reports errors in the same way as [`Parser::parse_recovery`].
+#[derive(Clone, Debug, Default)]
+pub struct ParseConfig {
+    pub(crate) cascade_distance: Option<usize>,
+    pub(crate) merge_errors: bool,
+    pub(crate) sort_errors: bool,
+    pub(crate) max_errors: Option<usize>,
+    pub(crate) log_recoveries: bool,
+    pub(crate) trace: bool,
+    pub(crate) profile: bool,
+    pub(crate) explain: bool,
+    pub(crate) stalled_loops: Option<StalledLoop>,
+    pub(crate) left_recursion: Option<LeftRecursion>,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) fuel: Option<usize>,
+    pub(crate) cancel: Option<Arc<AtomicBool>>,
+    pub(crate) progress: Option<Progress>,
+    pub(crate) cst: bool,
+    pub(crate) highlight: bool,
+    pub(crate) memoize: bool,
+    pub(crate) reparse: Option<(Memos, Edit)>,
+}
+
+impl ParseConfig {
+    /// Create a new configuration with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Suppress errors that overlap, or appear within `distance` inputs after, a region of the input that was
+    /// recovered from.
+    ///
+    /// After recovery, the errors that follow are frequently consequences of the original error rather than genuine
+    /// problems in their own right. The errors that caused the recovery are always kept.
+    pub fn suppress_cascading(mut self, distance: usize) -> Self {
+        self.cascade_distance = Some(distance);
+        self
+    }
+
+    /// Remove errors that are equal (according to [`PartialEq`]) to an earlier error at the same position in the
+    /// input, such as those reported by several recoveries of the same problem.
+    ///
+    /// Distinct errors at the same position (such as those of different alternatives) are all kept.
+    pub fn merge_errors(mut self) -> Self {
+        self.merge_errors = true;
+        self
+    }
+
+    /// Sort errors by their position in the input.
+    pub fn sort_errors(mut self) -> Self {
+        self.sort_errors = true;
+        self
+    }
+
+    /// Stop recovering from errors once the given number of errors have been recovered from, and report no more than
+    /// this number of errors.
+    ///
+    /// Once the limit is reached, the next error will cause parsing to stop.
+    pub fn max_errors(mut self, max: usize) -> Self {
+        self.max_errors = Some(max);
+        self
+    }
+
+    /// Record each recovery that occurs during parsing, available via [`ParseOutput::recoveries`].
+    pub fn log_recoveries(mut self) -> Self {
+        self.log_recoveries = true;
+        self
+    }
+
+    /// Record a [`Trace`] of every parser invocation during parsing, available via [`ParseOutput::trace`].
+    ///
+    /// The errors within the trace only mention the inputs involved if the stream can describe them (see
+    /// [`Stream::describe_tokens`]), as streams created from strings can.
+    ///
+    /// Tracing is slow and uses a lot of memory: it exists only to help you debug your parser.
+    pub fn trace(mut self) -> Self {
+        self.trace = true;
+        self
+    }
+
+    /// Record how often each parser site is invoked and how long is spent in it, available as a [`Profile`] via
+    /// [`ParseOutput::profile`].
+    ///
+    /// Sites are the parsers labelled with [`Parser::debug`]. Profiling adds overhead to every parser invocation, so
+    /// absolute times are only a rough guide: compare sites against one another instead.
+    pub fn profile(mut self) -> Self {
+        self.profile = true;
+        self
+    }
+
+    /// If the parse generates errors, explain which parsers rejected the input at the furthest position reached,
+    /// available as an [`Explanation`] via [`ParseOutput::explanation`].
+    ///
+    /// Like [`ParseConfig::trace`], this is slow: it exists only to help you debug your grammar.
+    pub fn explain(mut self) -> Self {
+        self.explain = true;
+        self
+    }
+
+    /// Choose what happens when an iteration of a repeating parser (such as [`Parser::repeated`] or
+    /// [`Parser::separated_by`]) succeeds without consuming any input.
+    ///
+    /// Continuing to iterate would never terminate, so the repetition always stops at that point. By default, this
+    /// panics in debug builds (naming the source location of the repeating parser) and is otherwise silent.
+    pub fn on_stalled_loop(mut self, action: StalledLoop) -> Self {
+        self.stalled_loops = Some(action);
+        self
+    }
+
+    pub(crate) fn stalled_loop(&self) -> StalledLoop {
+        self.stalled_loops.unwrap_or(if cfg!(debug_assertions) {
+            StalledLoop::Panic
+        } else {
+            StalledLoop::Stop
+        })
+    }
+
+    /// Choose what happens when a [`Recursive`](crate::recursive::Recursive) parser is re-entered at the same position
+    /// in the input, without having consumed any input in between (i.e: the parser is left-recursive).
+    ///
+    /// Continuing to recurse would never terminate, so the inner invocation always fails. By default, this panics in
+    /// debug builds (naming the source location at which the recursive parser was declared) and otherwise generates an
+    /// error. Note that, like any other error, this error may be discarded if an alternative succeeds.
+    pub fn on_left_recursion(mut self, action: LeftRecursion) -> Self {
+        self.left_recursion = Some(action);
+        self
+    }
+
+    pub(crate) fn left_recursion(&self) -> LeftRecursion {
+        self.left_recursion.unwrap_or(if cfg!(debug_assertions) {
+            LeftRecursion::Panic
+        } else {
+            LeftRecursion::Error
+        })
+    }
+
+    /// Limit the number of [`Recursive`](crate::recursive::Recursive) parsers that may be nested within one another
+    /// (such as by deeply nested brackets) before the parse is abandoned.
+    ///
+    /// See [`ParseConfig::fuel`] for what happens when a limit is exceeded.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Limit the total number of parser invocations that may occur during the parse (including those that are
+    /// backtracked over) before the parse is abandoned. This protects against input that would otherwise cause
+    /// excessive backtracking.
+    ///
+    /// When a limit is exceeded, the parse stops recovering from errors and fails as soon as possible. It produces no
+    /// output and a single error created by [`Error::limit_exceeded`], and the limit is reported by
+    /// [`ParseOutput::limit_exceeded`].
+    pub fn fuel(mut self, fuel: usize) -> Self {
+        self.fuel = Some(fuel);
+        self
+    }
+
+    /// Cancel the parse once the given flag is set (from another thread, for example).
+    ///
+    /// The flag is checked periodically rather than continuously, so the parse may continue briefly after it is set.
+    /// Once cancelled, the parse fails as soon as possible, in the same way as when a limit is exceeded (see
+    /// [`ParseConfig::fuel`]). It produces no output and a single error created by [`Error::cancelled`], and
+    /// [`ParseOutput::is_cancelled`] returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, ParseConfig};
+    /// use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+    ///
+    /// let cancel = Arc::new(AtomicBool::new(false));
+    /// let parser = text::ident::<_, Simple<char>>().padded().repeated();
+    ///
+    /// let config = ParseConfig::new().cancel_on(cancel.clone());
+    /// assert!(!parser.parse_recovery_with("foo bar", config.clone()).is_cancelled());
+    ///
+    /// cancel.store(true, Ordering::Relaxed);
+    /// assert!(parser.parse_recovery_with("foo bar", config).is_cancelled());
+    /// ```
+    pub fn cancel_on(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(flag);
+        self
+    }
+
+    /// Periodically report the progress of the parse by calling the given function with the current offset into the
+    /// input (such as the number of `char`s parsed so far, when parsing a string).
+    ///
+    /// The offset may decrease between calls when the parser backtracks.
+    pub fn on_progress<F: Fn(usize) + Send + Sync + 'static>(mut self, f: F) -> Self {
+        self.progress = Some(Progress(Arc::new(f)));
+        self
+    }
+
+    /// Build a lossless concrete syntax tree of the input, available as a [`CstNode`] via [`ParseOutput::cst`].
+    ///
+    /// Each parser labelled with [`Parser::labelled`] that contributes to the output becomes a node of the tree, and
+    /// every input becomes a leaf, so the tree can be used to reproduce the exact input. Labelled parsers that are
+    /// created during parsing (such as by [`Parser::then_with`]) or that are hidden within custom parsers do not create
+    /// nodes. See the [`cst`] module for more information.
+    pub fn cst(mut self) -> Self {
+        self.cst = true;
+        self
+    }
+
+    /// Classify the input for the sake of syntax highlighting, available via [`ParseOutput::highlights`].
+    ///
+    /// The input parsed by each parser tagged with [`Parser::highlighted`] that contributed to the result is given the
+    /// parser's class. See the [`highlight`] module for more information.
+    pub fn highlight(mut self) -> Self {
+        self.highlight = true;
+        self
+    }
+
+    /// Remember the results of memoized parsers (see [`Parser::memoized`]), so that a later parse of the edited input
+    /// can reuse them (see [`ParseConfig::reparse`]).
+    pub fn memoize(mut self) -> Self {
+        self.memoize = true;
+        self
+    }
+
+    /// Reparse the input after an edit, reusing the results of the memoized parsers from a previous parse (which must
+    /// have had [`ParseConfig::memoize`] enabled) that the edit could not have affected. See the [`incremental`]
+    /// module for more information.
+    ///
+    /// The previous output remembers everything needed to reuse its results, so only the new input need be given.
+    /// Nothing is reused if the previous parse used a different parser (or the same parser at a different address)
+    /// or different settings of [`ParseConfig::cst`] and [`ParseConfig::highlight`]. This also enables
+    /// [`ParseConfig::memoize`], so that the reparse can itself be followed by another.
+    pub fn reparse<I, O, E: Error<I>>(
+        mut self,
+        previous: &ParseOutput<I, O, E>,
+        edit: Edit,
+    ) -> Self {
+        self.memoize = true;
+        self.reparse = previous.memos.clone().map(|memos| (memos, edit));
+        self
+    }
+
+    pub(crate) fn finish<I, E: Error<I>>(
+        &self,
+        mut errors: Vec<Located<I, E>>,
+        same_error: Option<SameError<E>>,
+    ) -> Vec<Located<I, E>> {
+        if let Some(same_error) = same_error.filter(|_| self.merge_errors) {
+            let mut positions = BTreeMap::<usize, Vec<usize>>::new();
+            let mut keep = vec![true; errors.len()];
+            for (idx, error) in errors.iter().enumerate() {
+                let earlier = positions.entry(error.at).or_default();
+                if earlier
+                    .iter()
+                    .any(|&other| same_error(&errors[other].error, &error.error))
+                {
+                    keep[idx] = false;
+                } else {
+                    earlier.push(idx);
+                }
+            }
+            let mut keep = keep.into_iter();
+            errors.retain(|_| keep.next().unwrap_or(true));
+        }
+
+        if let Some(distance) = self.cascade_distance {
+            let mut order = (0..errors.len()).collect::<Vec<_>>();
+            order.sort_by_key(|idx| errors[*idx].at);
+
+            let mut keep = vec![true; errors.len()];
+            let mut window: Option<(usize, usize)> = None;
+            for idx in order {
+                let error = &errors[idx];
+                match window {
+                    Some((id, end))
+                        if error.at <= end && !matches!(error.region, Some(r) if r.id == id) =>
+                    {
+                        keep[idx] = false
+                    }
+                    _ => {
+                        if let Some(region) = error.region {
+                            let end = region.end + distance;
+                            window = Some(match window {
+                                Some((id, old_end)) if id == region.id => (id, old_end.max(end)),
+                                _ => (region.id, end),
+                            });
+                        }
+                    }
+                }
+            }
+
+            let mut keep = keep.into_iter();
+            errors.retain(|_| keep.next().unwrap_or(true));
+        }
+
+        if self.sort_errors {
+            errors.sort_by_key(|e| e.at);
+        }
+
+        if let Some(max) = self.max_errors {
+            errors.truncate(max);
+        }
+
+        errors
+    }
+}
+
+/// What to do when an iteration of a repeating parser succeeds without consuming any input. See
+/// [`ParseConfig::on_stalled_loop`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StalledLoop {
+    /// Silently stop repeating, keeping the outputs produced so far.
+    Stop,
+    /// Stop repeating and generate an error (see [`Error::stalled_loop`]).
+    Error,
+    /// Panic with a message naming the source location of the repeating parser.
+    Panic,
+}
+
+/// What to do when a [`Recursive`](crate::recursive::Recursive) parser is re-entered without consuming any input.
+/// See [`ParseConfig::on_left_recursion`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LeftRecursion {
+    /// Fail the inner invocation, generating an error (see [`Error::left_recursion`]).
+    Error,
+    /// Panic with a message naming the source location at which the recursive parser was declared.
+    Panic,
+}
+
+/// A limit on the resources that a parse may use. See [`ParseConfig::max_depth`] and [`ParseConfig::fuel`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Limit {
+    /// The maximum nesting depth of recursive parsers.
+    Depth(usize),
+    /// The maximum number of parser invocations.
+    Fuel(usize),
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Depth(depth) => write!(f, "maximum recursion depth of {}", depth),
+            Self::Fuel(fuel) => write!(f, "fuel limit of {} parser invocations", fuel),
+        }
+    }
+}
+
+/// A callback that reports the progress of a parse. See [`ParseConfig::on_progress`].
+#[derive(Clone)]
+pub(crate) struct Progress(pub(crate) Arc<dyn Fn(usize) + Send + Sync>);
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Progress")
+    }
+}
+
+/// The reason that a parse was abandoned before it finished.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Abandon {
+    Limit(Limit),
+    Cancelled,
+}
+
+impl Abandon {
+    pub(crate) fn error<I, E: Error<I>>(self, span: E::Span) -> E {
+        match self {
+            Self::Limit(limit) => E::limit_exceeded(span, limit),
+            Self::Cancelled => E::cancelled(span),
+        }
+    }
+}
+
+/// A region of the input that was recovered from.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Region {
+    pub(crate) id: usize,
+    pub(crate) end: usize,
+}
+
+/// A recovery that occurred during parsing, recorded for the sake of [`RecoveryEvent`].
+pub(crate) struct RecoveryRecord {
+    pub(crate) id: usize,
+    pub(crate) strategy: &'static str,
+    pub(crate) location: Location<'static>,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+/// A parser that succeeded or a recovery that occurred during parsing, recorded for the sake of [`CstNode`]s,
+/// [`Trivia`], highlighting and [`ParseConfig::max_errors`].
+#[derive(Clone)]
+pub(crate) struct Record {
+    pub(crate) kind: RecordKind,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+/// The kind of parser that a [`Record`] was made for.
+#[derive(Copy, Clone)]
+pub(crate) enum RecordKind {
+    /// A labelled parser, identified by its [`Site`] (see [`ParseConfig::cst`]).
+    Node(Site),
+    /// A trivia parser (see [`Parser::record_trivia`]).
+    Trivia,
+    /// A highlighted parser, along with its class (see [`ParseConfig::highlight`]).
+    Highlight(HighlightClass),
+    /// A recovery from errors, counted towards [`ParseConfig::max_errors`] unless it is backtracked over.
+    Recovery,
+}
+
+/// State that persists throughout a single parse.
+#[derive(Default)]
+pub(crate) struct ParseState {
+    pub(crate) config: ParseConfig,
+    /// The number of recoveries so far, including those that were backtracked over, which identifies the next
+    /// [`Region`].
+    pub(crate) recoveries: usize,
+    pub(crate) log: Vec<RecoveryRecord>,
+    /// The recursive parsers currently being invoked, along with the offsets at which they were invoked.
+    pub(crate) recursion: Vec<(*const (), usize)>,
+    /// The number of parser invocations so far.
+    pub(crate) steps: usize,
+    /// Whether the parse is looking for completions, and so should record the contexts of errors (see
+    /// [`Parser::expected_at`]).
+    pub(crate) completing: bool,
+    /// The reason that the parse was abandoned, if any, along with the offset at which it was abandoned.
+    pub(crate) abandoned: Option<(Abandon, usize)>,
+    /// The labelled parsers (if building a concrete syntax tree, see [`ParseConfig::cst`]), highlighted parsers (if
+    /// highlighting, see [`ParseConfig::highlight`]) and trivia parsers (see [`Parser::record_trivia`]) that have
+    /// succeeded so far, along with the recoveries that have occurred, which must be discarded when backtracking.
+    pub(crate) records: Vec<Record>,
+    /// The number of recoveries among the [`ParseState::records`], which are those counted towards
+    /// [`ParseConfig::max_errors`].
+    pub(crate) recorded_recoveries: usize,
+    /// The offset following the furthest input examined so far (see [`Parser::memoized`]).
+    pub(crate) reach: usize,
+    /// The results of the memoized parsers that have finished so far, outside of any other memoized parser (see
+    /// [`ParseConfig::memoize`]).
+    pub(crate) memos: Vec<Slot>,
+    /// The results of a previous parse that can be reused, keyed by offset and site (see [`ParseConfig::reparse`]).
+    pub(crate) reusable: BTreeMap<(usize, Site), Slot>,
+}
+
+/// A record of a recovery that occurred during parsing. See [`ParseConfig::log_recoveries`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecoveryEvent<S> {
+    pub(crate) strategy: &'static str,
+    pub(crate) location: Location<'static>,
+    pub(crate) span: S,
+    pub(crate) skipped: usize,
+    pub(crate) errors: Vec<usize>,
+}
+
+impl<S> RecoveryEvent<S> {
+    /// The name of the strategy that performed the recovery (see [`Strategy::name`]).
+    pub fn strategy(&self) -> &'static str {
+        self.strategy
+    }
+
+    /// The location in the source code of the parser that performed the recovery (such as a call to
+    /// [`Parser::recover_with`]).
+    pub fn location(&self) -> Location<'static> {
+        self.location
+    }
+
+    /// The span of the input that was recovered from.
+    pub fn span(&self) -> &S {
+        &self.span
+    }
+
+    /// The number of inputs that were recovered from.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// The indices, within [`ParseOutput::errors`], of the errors that caused the recovery.
+    ///
+    /// This may be empty if those errors were removed by the [`ParseConfig`].
+    pub fn errors(&self) -> &[usize] {
+        &self.errors
+    }
+}
+
+/// The output of a parse, produced by [`Parser::parse_recovery_with`].
+pub struct ParseOutput<I, O, E: Error<I>> {
+    pub(crate) output: Option<O>,
+    pub(crate) errors: Vec<E>,
+    pub(crate) recoveries: Vec<RecoveryEvent<E::Span>>,
+    pub(crate) trace: Option<Trace>,
+    pub(crate) profile: Option<Profile>,
+    pub(crate) explanation: Option<Explanation>,
+    pub(crate) abandoned: Option<Abandon>,
+    pub(crate) incomplete: Option<Vec<usize>>,
+    pub(crate) cst: Option<CstNode<I, E::Span, E::Label>>,
+    pub(crate) highlights: Vec<(E::Span, HighlightClass)>,
+    pub(crate) memos: Option<Memos>,
+    pub(crate) phantom: PhantomData<I>,
+}
+
+impl<I: Clone, O: Clone, E: Error<I> + Clone> Clone for ParseOutput<I, O, E>
+where
+    E::Label: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            output: self.output.clone(),
+            errors: self.errors.clone(),
+            recoveries: self.recoveries.clone(),
+            trace: self.trace.clone(),
+            profile: self.profile.clone(),
+            explanation: self.explanation.clone(),
+            abandoned: self.abandoned,
+            incomplete: self.incomplete.clone(),
+            cst: self.cst.clone(),
+            highlights: self.highlights.clone(),
+            memos: self.memos.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Outputs are compared without regard to the results remembered for reparsing (see [`ParseConfig::memoize`]).
+impl<I: PartialEq, O: PartialEq, E: Error<I> + PartialEq> PartialEq for ParseOutput<I, O, E>
+where
+    E::Span: PartialEq,
+    E::Label: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.output == other.output
+            && self.errors == other.errors
+            && self.recoveries == other.recoveries
+            && self.trace == other.trace
+            && self.profile == other.profile
+            && self.explanation == other.explanation
+            && self.abandoned == other.abandoned
+            && self.incomplete == other.incomplete
+            && self.cst == other.cst
+            && self.highlights == other.highlights
+    }
+}
+
+impl<I, O: fmt::Debug, E: Error<I> + fmt::Debug> fmt::Debug for ParseOutput<I, O, E>
+where
+    E::Span: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ParseOutput")
+            .field("output", &self.output)
+            .field("errors", &self.errors)
+            .field("recoveries", &self.recoveries)
+            .field("trace", &self.trace)
+            .field("profile", &self.profile)
+            .field("explanation", &self.explanation)
+            .field("limit_exceeded", &self.limit_exceeded())
+            .field("cancelled", &self.is_cancelled())
+            .field("incomplete", &self.incomplete)
+            .finish()
+    }
+}
+
+impl<I, O, E: Error<I>> ParseOutput<I, O, E> {
+    /// Get the output of the parse, if any was produced.
+    pub fn output(&self) -> Option<&O> {
+        self.output.as_ref()
+    }
+
+    /// Get the errors generated by the parse.
+    pub fn errors(&self) -> &[E] {
+        &self.errors
+    }
+
+    /// Returns `true` if the parse produced an output.
+    pub fn has_output(&self) -> bool {
+        self.output.is_some()
+    }
+
+    /// Returns `true` if the parse generated any errors.
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Get the recoveries that occurred during the parse, if [`ParseConfig::log_recoveries`] was enabled.
+    ///
+    /// Only recoveries that contributed to the final output are included.
+    pub fn recoveries(&self) -> &[RecoveryEvent<E::Span>] {
+        &self.recoveries
+    }
+
+    /// Get the trace of the parse, if [`ParseConfig::trace`] was enabled.
+    pub fn trace(&self) -> Option<&Trace> {
+        self.trace.as_ref()
+    }
+
+    /// Get the profile of the parse, if [`ParseConfig::profile`] was enabled.
+    pub fn profile(&self) -> Option<&Profile> {
+        self.profile.as_ref()
+    }
+
+    /// Get the explanation of the errors generated by the parse, if [`ParseConfig::explain`] was enabled and the parse
+    /// generated errors.
+    pub fn explanation(&self) -> Option<&Explanation> {
+        self.explanation.as_ref()
+    }
+
+    /// Get the limit that the parse exceeded, if any (see [`ParseConfig::max_depth`] and [`ParseConfig::fuel`]).
+    ///
+    /// A parse that exceeded a limit was abandoned, so it produced no output.
+    pub fn limit_exceeded(&self) -> Option<Limit> {
+        match self.abandoned {
+            Some(Abandon::Limit(limit)) => Some(limit),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the parse was cancelled (see [`ParseConfig::cancel_on`]).
+    ///
+    /// A parse that was cancelled was abandoned, so it produced no output.
+    pub fn is_cancelled(&self) -> bool {
+        self.abandoned == Some(Abandon::Cancelled)
+    }
+
+    /// Returns `true` if the parse generated errors, but every error at the furthest position reached was caused by
+    /// reaching the end of the input (see [`Error::is_unexpected_end`]). Providing more input might allow the parse to
+    /// succeed (such as in a REPL, where the user should be prompted to continue typing).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, ParseConfig};
+    /// let block = just::<_, _, Simple<char>>('x')
+    ///     .padded()
+    ///     .repeated()
+    ///     .delimited_by_balanced('{', '}')
+    ///     .then_ignore(end());
+    ///
+    /// let output = block.parse_recovery_with("{ x x", ParseConfig::new());
+    /// assert!(output.is_incomplete());
+    /// assert_eq!(output.unclosed_delimiters().collect::<Vec<_>>(), vec![(&(0..1), &'{')]);
+    ///
+    /// // More input cannot fix a genuine syntax error
+    /// let output = block.parse_recovery_with("{ x y", ParseConfig::new());
+    /// assert!(!output.is_incomplete());
+    /// ```
+    pub fn is_incomplete(&self) -> bool {
+        self.incomplete.is_some()
+    }
+
+    /// Get the delimiters left unclosed at the end of the input (see [`Error::unclosed`]), if the parse was
+    /// incomplete (see [`ParseOutput::is_incomplete`]).
+    pub fn unclosed_delimiters(&self) -> impl Iterator<Item = (&E::Span, &I)> + '_ {
+        self.incomplete
+            .iter()
+            .flatten()
+            .filter_map(move |i| self.errors[*i].unclosed())
+    }
+
+    /// Get the concrete syntax tree of the input, if [`ParseConfig::cst`] was enabled and the parse produced an output.
+    pub fn cst(&self) -> Option<&CstNode<I, E::Span, E::Label>> {
+        self.cst.as_ref()
+    }
+
+    /// Get the highlighted ranges of the input, in order, if [`ParseConfig::highlight`] was enabled.
+    ///
+    /// The ranges never overlap, and inputs that were not parsed by a highlighted parser are not covered by any range.
+    pub fn highlights(&self) -> &[(E::Span, HighlightClass)] {
+        &self.highlights
+    }
+
+    /// Take the output of the parse, discarding any errors.
+    pub fn into_output(self) -> Option<O> {
+        self.output
+    }
+
+    /// Take the errors generated by the parse, discarding any output.
+    pub fn into_errors(self) -> Vec<E> {
+        self.errors
+    }
+
+    /// Take both the output of and the errors generated by the parse, in the form produced by
+    /// [`Parser::parse_recovery`].
+    pub fn into_output_errors(self) -> (Option<O>, Vec<E>) {
+        (self.output, self.errors)
+    }
+
+    /// Convert the output into a [`Result`], in the form produced by [`Parser::parse`].
+    ///
+    /// If the parse generated errors, they are returned even if an output was produced through recovery.
+    pub fn into_result(self) -> Result<O, Vec<E>> {
+        if self.errors.is_empty() {
+            Ok(self.output.expect(
+                "Parsing failed, but no errors were emitted. This is troubling, to say the least.",
+            ))
+        } else {
+            Err(self.errors)
+        }
+    }
+}
+
+/// The inputs that could come next at a position in the input, produced by [`Parser::expected_at`].
+pub struct Completion<I, E: Error<I>> {
+    pub(crate) error: Option<E>,
+    pub(crate) contexts: Vec<E::Label>,
+    pub(crate) phantom: PhantomData<I>,
+}
+
+impl<I, E: Error<I> + fmt::Debug> fmt::Debug for Completion<I, E>
+where
+    E::Label: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Completion")
+            .field("error", &self.error)
+            .field("contexts", &self.contexts)
+            .finish()
+    }
+}
+
+impl<I, E: Error<I>> Completion<I, E> {
+    /// Get the merged error generated at the position, describing the inputs that were expected there, if the parse
+    /// reached the position.
+    pub fn error(&self) -> Option<&E> {
+        self.error.as_ref()
+    }
+
+    /// Get the labels of the labelled parsers (see [`Parser::labelled`]) enclosing the position, innermost first.
+    pub fn contexts(&self) -> &[E::Label] {
+        &self.contexts
+    }
+
+    /// Take the merged error generated at the position, if any.
+    pub fn into_error(self) -> Option<E> {
+        self.error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::{Cheap, Simple, SimpleReason},
+        text::TextParser,
+    };
+
+    #[test]
+    fn stalled_loop() {
+        let parser = just::<_, _, Simple<char>>('a').or_not().repeated();
+        let line = line!() - 1;
+
+        let output = parser
+            .parse_recovery_with("aab", ParseConfig::new().on_stalled_loop(StalledLoop::Stop));
+        assert_eq!(output.output(), Some(&vec![Some('a'), Some('a'), None]));
+        assert!(!output.has_errors());
+
+        let output = parser.parse_recovery_with(
+            "aab",
+            ParseConfig::new().on_stalled_loop(StalledLoop::Error),
+        );
+        assert_eq!(output.output(), Some(&vec![Some('a'), Some('a'), None]));
+        assert_eq!(output.errors().len(), 1);
+        assert_eq!(output.errors()[0].span(), 2..2);
+        assert!(matches!(
+            output.errors()[0].reason(),
+            SimpleReason::Custom(msg) if msg.contains(&format!("{}:{}", file!(), line)),
+        ));
+
+        let parser = just::<_, _, Simple<char>>('a')
+            .or_not()
+            .separated_by(just(',').or_not())
+            .at_most(2);
+        let output = parser.parse_recovery_with(
+            "a,b",
+            ParseConfig::new().on_stalled_loop(StalledLoop::Error),
+        );
+        assert_eq!(output.output(), Some(&vec![Some('a'), None]));
+        assert!(!output.has_errors());
+
+        let output = parser.at_most(4).parse_recovery_with(
+            "a,b",
+            ParseConfig::new().on_stalled_loop(StalledLoop::Error),
+        );
+        assert_eq!(output.output(), Some(&vec![Some('a'), None, None]));
+        assert_eq!(output.errors().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "succeeded without consuming any input")]
+    fn stalled_loop_panic() {
+        let _ = text::whitespace::<_, Simple<char>>()
+            .repeated()
+            .parse_recovery_with(
+                "  a",
+                ParseConfig::new().on_stalled_loop(StalledLoop::Panic),
+            );
+    }
+
+    #[test]
+    fn left_recursion() {
+        let parser = recursive::recursive::<_, _, _, _, Simple<char>>(|expr| {
+            expr.then_ignore(just('+'))
+                .then(text::int(10))
+                .map(|(a, b): (String, String)| a + &b)
+        });
+        let line = line!() - 5;
+
+        let output = parser.parse_recovery_with(
+            "1+2",
+            ParseConfig::new().on_left_recursion(LeftRecursion::Error),
+        );
+        assert!(!output.has_output());
+        assert_eq!(output.errors().len(), 1);
+        assert_eq!(output.errors()[0].span(), 0..0);
+        assert!(matches!(
+            output.errors()[0].reason(),
+            SimpleReason::Custom(msg) if msg.contains(&format!("{}:{}", file!(), line)),
+        ));
+
+        // Re-entering at a later offset is not left recursion
+        let parser = recursive::recursive::<_, _, _, _, Simple<char>>(|expr| {
+            just('-').ignore_then(expr).or(text::int(10))
+        });
+        assert_eq!(parser.parse("--1"), Ok("1".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "was re-entered without consuming any input")]
+    fn left_recursion_panic() {
+        let parser = recursive::recursive::<_, _, _, _, Simple<char>>(|expr| {
+            expr.then_ignore(just('+')).or(text::int(10))
+        });
+        let _ = parser.parse_recovery_with(
+            "1+2",
+            ParseConfig::new().on_left_recursion(LeftRecursion::Panic),
+        );
+    }
+
+    #[test]
+    fn parse_limits() {
+        let parser = recursive::recursive::<_, _, _, _, Simple<char>>(|expr| {
+            expr.delimited_by(just('('), just(')')).or(text::int(10))
+        })
+        .then_ignore(end());
+
+        let output = parser.parse_recovery_with("(((1)))", ParseConfig::new().max_depth(4));
+        assert_eq!(output.output(), Some(&"1".to_string()));
+        assert_eq!(output.limit_exceeded(), None);
+
+        let output = parser.parse_recovery_with("((((1))))", ParseConfig::new().max_depth(4));
+        assert_eq!(output.output(), None);
+        assert_eq!(output.limit_exceeded(), Some(Limit::Depth(4)));
+        assert_eq!(output.errors().len(), 1);
+        assert_eq!(output.errors()[0].span(), 4..4);
+
+        let output = parser.parse_recovery_with("(((1)))", ParseConfig::new().fuel(10));
+        assert_eq!(output.output(), None);
+        assert_eq!(output.limit_exceeded(), Some(Limit::Fuel(10)));
+        assert_eq!(output.errors().len(), 1);
+        assert_eq!(
+            output.errors()[0].reason(),
+            &SimpleReason::Custom(
+                "parse exceeded the fuel limit of 10 parser invocations".to_string()
+            )
+        );
+
+        let output = parser.parse_recovery_with("(((1)))", ParseConfig::new().fuel(1000));
+        assert_eq!(output.output(), Some(&"1".to_string()));
+    }
+
+    #[test]
+    fn parse_progress() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let reports = Arc::new(AtomicUsize::new(0));
+        let furthest = Arc::new(AtomicUsize::new(0));
+        let config = {
+            let (reports, furthest) = (reports.clone(), furthest.clone());
+            ParseConfig::new().on_progress(move |offset| {
+                reports.fetch_add(1, Ordering::Relaxed);
+                furthest.fetch_max(offset, Ordering::Relaxed);
+            })
+        };
+
+        let input = "a".repeat(10_000);
+        let output = just::<_, _, Simple<char>>('a')
+            .repeated()
+            .then_ignore(end())
+            .parse_recovery_with(input.as_str(), config);
+        assert_eq!(output.output().map(Vec::len), Some(10_000));
+        assert!(reports.load(Ordering::Relaxed) > 1);
+        assert!(furthest.load(Ordering::Relaxed) > 5_000);
+    }
+
+    #[test]
+    fn incomplete_input() {
+        let parser = text::int::<_, Simple<char>>(10)
+            .padded()
+            .separated_by(just('+'))
+            .delimited_by(just('('), just(')'))
+            .then_ignore(end());
+
+        let config = ParseConfig::new;
+        assert!(!parser
+            .parse_recovery_with("(1 + 2)", config())
+            .is_incomplete());
+        assert!(parser
+            .parse_recovery_with("(1 + 2", config())
+            .is_incomplete());
+        assert!(parser.parse_recovery_with("(1 +", config()).is_incomplete());
+        assert!(!parser
+            .parse_recovery_with("(1 + )", config())
+            .is_incomplete());
+        assert!(!parser
+            .parse_recovery_with("(1 + 2))", config())
+            .is_incomplete());
+
+        let output = parser.parse_recovery_with("(1 + 2", config());
+        assert_eq!(output.unclosed_delimiters().count(), 0);
+
+        // Errors at the end of the input that more input could not avoid
+        let short = text::int::<_, Simple<char>>(10)
+            .try_map(|s, span| {
+                if s.len() < 5 {
+                    Ok(s)
+                } else {
+                    Err(Simple::custom(span, "too long"))
+                }
+            })
+            .then_ignore(end());
+        assert!(!short.parse_recovery_with("12345", config()).is_incomplete());
+        let warned = text::int::<_, Simple<char>>(10)
+            .validate(|s, span, emit| {
+                emit(Simple::custom(span, "leading zero"));
+                s
+            })
+            .then_ignore(end());
+        assert!(!warned.parse_recovery_with("12", config()).is_incomplete());
+        let cheap = just::<_, _, Cheap<char>>('(')
+            .then(just(')'))
+            .then_ignore(end());
+        assert!(cheap.parse_recovery_with("(", config()).is_incomplete());
+        assert!(!cheap.parse_recovery_with("(]", config()).is_incomplete());
+    }
+
+    #[test]
+    fn expected_at_cursor() {
+        let args = text::int::<_, Simple<char>>(10)
+            .padded()
+            .separated_by(just(','))
+            .delimited_by(just('('), just(')'))
+            .labelled("args");
+        let call = text::ident().then(args.or_not()).labelled("call");
+
+        // The parser succeeds at the cursor, but could have continued with arguments
+        let completion = call.expected_at("foo", 3);
+        let error = completion.error().unwrap();
+        assert_eq!(error.span(), 3..3);
+        assert!(error.expected().any(|e| e == &Some('(')));
+        assert_eq!(completion.contexts(), &["args", "call"]);
+
+        let completion = call.expected_at("foo(1, 2", 8);
+        let error = completion.error().unwrap();
+        assert!(error.expected().any(|e| e == &Some(')')));
+        assert_eq!(completion.contexts(), &["args", "call"]);
+
+        // The parser never reaches the cursor
+        let completion = call.expected_at("(foo", 4);
+        assert!(completion.error().is_none());
+        assert!(completion.contexts().is_empty());
+    }
+}
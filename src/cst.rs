@@ -0,0 +1,290 @@
+//! Lossless concrete syntax trees.
+//!
+//! *“Nothing travels faster than the speed of light with the possible exception of bad news, which obeys its own
+//! special laws.”*
+//!
+//! When [`ParseConfig::cst`] is enabled, a parse builds a [`CstNode`] tree alongside its output. Every parser labelled
+//! with [`Parser::labelled`] that contributed to the output becomes a node, and every input becomes a leaf (including
+//! those consumed by parsers like [`TextParser::padded`](crate::text::TextParser::padded) and
+//! [`Parser::ignore_then`], those skipped during recovery, and those hidden by [`Stream::from_iter_with_trivia`]), so
+//! the tree can be written back out as the exact input that produced it.
+
+use super::*;
+use crate::stream::Hidden;
+
+/// Identifies a parser by its address and type. The type is needed because a parser may share its address with the
+/// parser it wraps.
+pub(crate) type Site = (usize, &'static str);
+
+pub(crate) fn site<P>(parser: &P) -> Site {
+    (parser as *const P as usize, core::any::type_name::<P>())
+}
+
+/// An element of a [`CstNode`]: either a nested node or a single input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CstElement<I, S, L> {
+    /// A node created by a labelled parser.
+    Node(CstNode<I, S, L>),
+    /// An input, along with its span.
+    Token(I, S),
+}
+
+/// A node of a concrete syntax tree, covering the inputs parsed by a labelled parser. See [`ParseConfig::cst`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, ParseConfig, cst::CstElement};
+/// let int = text::int::<_, Simple<char>>(10).labelled("int");
+/// let sum = int
+///     .padded()
+///     .separated_by(just('+'))
+///     .labelled("sum");
+///
+/// let output = sum.parse_recovery_with(" 1 + 23", ParseConfig::new().cst());
+/// let cst = output.cst().unwrap();
+///
+/// // The tree covers every input, including whitespace
+/// assert_eq!(cst.to_string(), " 1 + 23");
+///
+/// let sum = match &cst.children()[0] {
+///     CstElement::Node(node) => node,
+///     CstElement::Token(..) => unreachable!(),
+/// };
+/// assert_eq!(sum.kind(), Some(&"sum"));
+/// assert_eq!(sum.nodes().map(|node| node.to_string()).collect::<Vec<_>>(), vec!["1", "23"]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CstNode<I, S, L> {
+    kind: Option<Arc<L>>,
+    offsets: Range<usize>,
+    span: S,
+    children: Vec<CstElement<I, S, L>>,
+}
+
+impl<I, S, L> CstNode<I, S, L> {
+    /// The label of the parser that created the node, or `None` for the root of the tree.
+    pub fn kind(&self) -> Option<&L> {
+        self.kind.as_deref()
+    }
+
+    /// The offsets of the inputs that the node covers.
+    pub fn offsets(&self) -> Range<usize> {
+        self.offsets.clone()
+    }
+
+    /// The span of the inputs that the node covers.
+    pub fn span(&self) -> &S {
+        &self.span
+    }
+
+    /// The nodes and inputs within the node, in order.
+    pub fn children(&self) -> &[CstElement<I, S, L>] {
+        &self.children
+    }
+
+    /// The nodes directly within the node, in order.
+    pub fn nodes(&self) -> impl Iterator<Item = &Self> + '_ {
+        self.children.iter().filter_map(|child| match child {
+            CstElement::Node(node) => Some(node),
+            CstElement::Token(..) => None,
+        })
+    }
+
+    /// Every input that the node covers, along with its span, in order.
+    pub fn tokens(&self) -> impl Iterator<Item = (&I, &S)> + '_ {
+        self.children
+            .iter()
+            .flat_map(|child| -> Box<dyn Iterator<Item = (&I, &S)> + '_> {
+                match child {
+                    CstElement::Node(node) => Box::new(node.tokens()),
+                    CstElement::Token(token, span) => Box::new(core::iter::once((token, span))),
+                }
+            })
+    }
+}
+
+/// Writes every input that the node covers, reproducing the original input when parsing a string.
+impl<I: fmt::Display, S, L> fmt::Display for CstNode<I, S, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.tokens()
+            .try_for_each(|(token, _)| write!(f, "{}", token))
+    }
+}
+
+/// Build the tree covering `inputs` (and the `hidden` inputs between them) from the labelled parsers recorded while
+/// parsing them, given the labels of the parsers at each site.
+pub(crate) fn build<I: Clone, S: Span, L>(
+    records: Vec<Record>,
+    sites: &[(Site, Arc<L>)],
+    inputs: &[(I, S)],
+    hidden: &[Hidden<I, S>],
+    eoi: S,
+) -> CstNode<I, S, L> {
+    // Parsers created during parsing (by `Parser::then_with`, for example) have no known label, so create no nodes
+    let mut nodes = records
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, record)| {
+            let site = match record.kind {
+                RecordKind::Node(site) => site,
+                RecordKind::Trivia | RecordKind::Highlight(_) | RecordKind::Recovery => {
+                    return None
+                }
+            };
+            let (_, label) = sites.iter().find(|(s, _)| *s == site)?;
+            Some((record.start..record.end, i, label.clone()))
+        })
+        .collect::<Vec<_>>();
+    // Records are made as parsers finish, so enclosing nodes come first when sorted by reverse order of recording
+    nodes.sort_by(|(a, a_idx, _), (b, b_idx, _)| {
+        a.start
+            .cmp(&b.start)
+            .then(b.end.cmp(&a.end))
+            .then(b_idx.cmp(a_idx))
+    });
+
+    let mut builder = Builder {
+        inputs,
+        hidden,
+        next_hidden: 0,
+        eoi,
+    };
+    let mut nodes = nodes.into_iter().peekable();
+    builder.node(None, 0..inputs.len(), &mut nodes)
+}
+
+struct Builder<'a, I, S> {
+    inputs: &'a [(I, S)],
+    hidden: &'a [Hidden<I, S>],
+    /// Elements are built in order, so hidden inputs are too.
+    next_hidden: usize,
+    eoi: S,
+}
+
+impl<'a, I: Clone, S: Span> Builder<'a, I, S> {
+    fn node<L, N: Iterator<Item = (Range<usize>, usize, Arc<L>)>>(
+        &mut self,
+        kind: Option<Arc<L>>,
+        offsets: Range<usize>,
+        nodes: &mut core::iter::Peekable<N>,
+    ) -> CstNode<I, S, L> {
+        // Hidden inputs at either end of a node belong to the node that encloses it
+        let is_root = kind.is_none();
+        let mut children = Vec::new();
+        let mut pos = offsets.start;
+        while let Some((range, _, _)) = nodes.peek() {
+            if range.start < pos {
+                // Overlaps the previous node without nesting within it, so it cannot be represented
+                nodes.next();
+                continue;
+            } else if range.end > offsets.end {
+                break;
+            }
+            let (range, _, label) = nodes.next().unwrap();
+            let leading = is_root || pos != offsets.start;
+            self.tokens(pos..range.start, leading, true, &mut children);
+            pos = range.end;
+            children.push(CstElement::Node(self.node(Some(label), range, nodes)));
+        }
+        let leading = is_root || pos != offsets.start;
+        self.tokens(pos..offsets.end, leading, is_root, &mut children);
+
+        CstNode {
+            kind,
+            span: self.span(offsets.clone()),
+            offsets,
+            children,
+        }
+    }
+
+    /// Add the inputs within `offsets` to `children`, each preceded by the hidden inputs before it. The hidden inputs
+    /// before the first input and after the last input are only added if `leading` and `trailing` respectively.
+    fn tokens<L>(
+        &mut self,
+        offsets: Range<usize>,
+        leading: bool,
+        trailing: bool,
+        children: &mut Vec<CstElement<I, S, L>>,
+    ) {
+        for at in offsets.start..=offsets.end {
+            if (at != offsets.start || leading) && (at != offsets.end || trailing) {
+                while let Some((_, (token, span))) =
+                    self.hidden.get(self.next_hidden).filter(|(i, _)| *i == at)
+                {
+                    children.push(CstElement::Token(token.clone(), span.clone()));
+                    self.next_hidden += 1;
+                }
+            }
+            if let Some((token, span)) = self.inputs.get(at).filter(|_| at < offsets.end) {
+                children.push(CstElement::Token(token.clone(), span.clone()));
+            }
+        }
+    }
+
+    fn span(&self, offsets: Range<usize>) -> S {
+        match (self.inputs.get(offsets.start), offsets.is_empty()) {
+            (Some((_, first)), false) => {
+                let (_, last) = &self.inputs[offsets.end - 1];
+                S::new(first.context(), first.start()..last.end())
+            }
+            (Some((_, next)), true) => S::new(next.context(), next.start()..next.start()),
+            (None, _) => self.eoi.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{error::Simple, text::TextParser};
+
+    /// Write out a tree with each node's children enclosed in square brackets, preceded by its label.
+    pub(crate) fn render(node: &CstNode<char, Range<usize>, &str>) -> String {
+        node.children()
+            .iter()
+            .map(|child| match child {
+                CstElement::Node(node) => format!("{}[{}]", node.kind().unwrap(), render(node)),
+                CstElement::Token(c, _) => c.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn concrete_syntax_tree() {
+        let ident = text::ident::<_, Simple<char>>().labelled("ident");
+        let call = ident
+            .clone()
+            .then_ignore(just('(').padded())
+            .then_ignore(just(')'))
+            .labelled("call");
+        let stmt = call
+            .map(|_| ())
+            .or(ident.labelled("var").map(|_| ()))
+            .padded()
+            .then_ignore(just(';'))
+            .labelled("stmt");
+        let parser = stmt.repeated().then_ignore(end());
+
+        let src = " foo ( ); bar ;";
+        let output = parser.parse_recovery_with(src, ParseConfig::new().cst());
+        let cst = output.cst().unwrap();
+
+        // The `ident` parsed while attempting `call` on `bar` was backtracked over, so does not appear
+        assert_eq!(cst.to_string(), src);
+        assert_eq!(
+            render(cst),
+            "stmt[ call[ident[foo] ( )];]stmt[ var[ident[bar]] ;]",
+        );
+        assert_eq!(cst.span(), &(0..15));
+        let stmts = cst.nodes().collect::<Vec<_>>();
+        assert_eq!(stmts[1].span(), &(9..15));
+        assert_eq!(stmts[1].offsets(), 9..15);
+
+        // Without the option, no tree is built
+        assert!(parser
+            .parse_recovery_with(src, ParseConfig::new())
+            .cst()
+            .is_none());
+    }
+}
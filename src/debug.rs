@@ -5,8 +5,14 @@
 
 use super::*;
 
-use alloc::borrow::Cow;
+use alloc::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::ToString,
+};
 use core::panic::Location;
+use core::time::Duration;
 
 /// Information about a specific parser.
 #[allow(dead_code)]
@@ -57,42 +63,798 @@ pub trait Debugger {
     ) -> PResult<I, O, P::Error>;
 }
 
-/// A verbose debugger that emits debugging messages to the console.
+/// The outcome of a single parser invocation within a [`Trace`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TraceOutcome {
+    /// The parser succeeded without generating any errors.
+    Success,
+    /// The parser succeeded, but only after recovering from one or more errors.
+    Recovered,
+    /// The parser failed.
+    Failure,
+}
+
+impl TraceOutcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Recovered => "recovered",
+            Self::Failure => "failure",
+        }
+    }
+}
+
+/// A record of a single parser invocation within a [`Trace`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceNode {
+    name: &'static str,
+    label: Option<String>,
+    location: Option<Location<'static>>,
+    start: usize,
+    end: usize,
+    outcome: TraceOutcome,
+    errors: Vec<TraceError>,
+    events: Vec<TraceEvent>,
+}
+
+impl TraceNode {
+    /// The name of the parser's type, such as `Map` or `Just`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The label given to the parser by [`Parser::debug`], if any.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// The location in the source code of the call to [`Parser::debug`], if any.
+    pub fn location(&self) -> Option<Location<'static>> {
+        self.location
+    }
+
+    /// The offsets of the inputs that the parser started and stopped at.
+    ///
+    /// For parsers that failed, the end is the position that the parser reached before failing.
+    pub fn offsets(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Whether the parser succeeded, recovered, or failed.
+    pub fn outcome(&self) -> TraceOutcome {
+        self.outcome
+    }
+
+    /// The errors that the parser produced, including the error that caused it to fail.
+    pub fn errors(&self) -> &[TraceError] {
+        &self.errors
+    }
+
+    /// The events that occurred while the parser was running, including invocations of the parsers within it.
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+}
+
+/// A description of an error for debugging output. See [`Error::describe`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ErrorDescription {
+    /// A message describing the error, such as the input that was found.
+    pub message: Option<String>,
+    /// The inputs that were expected, rendered as text, with `None` standing for the end of input.
+    pub expected: Vec<Option<String>>,
+    /// The label of the syntactic structure that was being parsed, if any.
+    pub label: Option<String>,
+}
+
+impl fmt::Display for ErrorDescription {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(message) = &self.message {
+            parts.push(message.clone());
+        }
+        if !self.expected.is_empty() {
+            let expected = self
+                .expected
+                .iter()
+                .map(|e| e.as_deref().unwrap_or("end of input"))
+                .collect::<Vec<_>>();
+            parts.push(format!("expected {}", expected.join(" or ")));
+        }
+        if let Some(label) = &self.label {
+            parts.push(format!("in {}", label));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// An error produced by a parser within a [`Trace`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceError {
+    at: usize,
+    description: ErrorDescription,
+}
+
+impl TraceError {
+    /// The offset of the input at which the error occurred.
+    pub fn at(&self) -> usize {
+        self.at
+    }
+
+    /// The description of the error, as given by [`Error::describe`].
+    pub fn description(&self) -> &ErrorDescription {
+        &self.description
+    }
+}
+
+/// An event recorded within a [`Trace`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TraceEvent {
+    /// A parser was invoked.
+    Invoke(TraceNode),
+    /// A parser was invoked at an earlier position in the input than the previous parser stopped at, abandoning the
+    /// inputs in between.
+    Backtrack {
+        /// The offset at which the previous parser stopped.
+        from: usize,
+        /// The offset at which the next parser started.
+        to: usize,
+    },
+    /// Debugging information was emitted.
+    Info(String),
+}
+
+/// A structured record of the parsers invoked during a parse. See [`ParseConfig::trace`].
+///
+/// The trace can be inspected programmatically, serialised with [`Trace::to_json`], or rendered as indented text with
+/// its [`fmt::Display`] implementation.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, ParseConfig, debug::{TraceEvent, TraceOutcome}};
+/// let parser = just::<_, _, Simple<char>>('a').or(just('b')).debug("a or b");
+///
+/// let output = parser.parse_recovery_with("b", ParseConfig::new().trace());
+/// let trace = output.trace().unwrap();
+///
+/// let root = match &trace.events()[0] {
+///     TraceEvent::Invoke(node) => node,
+///     _ => unreachable!(),
+/// };
+/// assert_eq!(root.label(), Some("a or b"));
+/// assert_eq!(root.outcome(), TraceOutcome::Success);
+/// assert_eq!(root.offsets(), 0..1);
+/// assert!(trace.to_json().starts_with("{\"events\":["));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trace {
+    events: Vec<TraceEvent>,
+}
+
+impl Trace {
+    /// The top-level events of the trace.
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+
+    /// Serialise the trace as JSON.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        json.push_str("{\"events\":");
+        write_json_events(&mut json, &self.events);
+        json.push('}');
+        json
+    }
+
+    /// Write the trace as indented text, in the same form as its [`fmt::Display`] implementation.
+    #[cfg(feature = "std")]
+    pub fn write<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<()> {
+        write!(writer, "{}", self)
+    }
+}
+
+fn write_json_str(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+fn write_json_opt_str(json: &mut String, s: Option<&str>) {
+    match s {
+        Some(s) => write_json_str(json, s),
+        None => json.push_str("null"),
+    }
+}
+
+fn write_json_error(json: &mut String, error: &TraceError) {
+    let description = &error.description;
+    json.push_str(&format!("{{\"at\":{},\"message\":", error.at));
+    write_json_opt_str(json, description.message.as_deref());
+    json.push_str(",\"expected\":[");
+    for (i, expected) in description.expected.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        write_json_opt_str(json, expected.as_deref());
+    }
+    json.push_str("],\"label\":");
+    write_json_opt_str(json, description.label.as_deref());
+    json.push('}');
+}
+
+fn write_json_events(json: &mut String, events: &[TraceEvent]) {
+    json.push('[');
+    for (i, event) in events.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        match event {
+            TraceEvent::Invoke(node) => {
+                json.push_str("{\"type\":\"invoke\",\"name\":");
+                write_json_str(json, node.name);
+                json.push_str(",\"label\":");
+                write_json_opt_str(json, node.label.as_deref());
+                json.push_str(",\"location\":");
+                match &node.location {
+                    Some(location) => {
+                        json.push_str("{\"file\":");
+                        write_json_str(json, location.file());
+                        json.push_str(&format!(
+                            ",\"line\":{},\"column\":{}}}",
+                            location.line(),
+                            location.column()
+                        ));
+                    }
+                    None => json.push_str("null"),
+                }
+                json.push_str(&format!(
+                    ",\"start\":{},\"end\":{},\"outcome\":\"{}\",\"errors\":[",
+                    node.start,
+                    node.end,
+                    node.outcome.as_str(),
+                ));
+                for (i, error) in node.errors.iter().enumerate() {
+                    if i > 0 {
+                        json.push(',');
+                    }
+                    write_json_error(json, error);
+                }
+                json.push_str("],\"events\":");
+                write_json_events(json, &node.events);
+                json.push('}');
+            }
+            TraceEvent::Backtrack { from, to } => json.push_str(&format!(
+                "{{\"type\":\"backtrack\",\"from\":{},\"to\":{}}}",
+                from, to
+            )),
+            TraceEvent::Info(s) => {
+                json.push_str("{\"type\":\"info\",\"message\":");
+                write_json_str(json, s);
+                json.push('}');
+            }
+        }
+    }
+    json.push(']');
+}
+
+fn fmt_events(f: &mut fmt::Formatter, events: &[TraceEvent], depth: usize) -> fmt::Result {
+    for event in events {
+        write!(f, "{:width$}", "", width = depth * 4)?;
+        match event {
+            TraceEvent::Invoke(node) => {
+                if let Some(label) = &node.label {
+                    write!(f, "{} ", label)?;
+                }
+                write!(f, "{}", node.name)?;
+                if let Some(location) = &node.location {
+                    write!(f, " (line {} in {})", location.line(), location.file())?;
+                }
+                write!(
+                    f,
+                    " at {}..{}: {}",
+                    node.start,
+                    node.end,
+                    node.outcome.as_str()
+                )?;
+                for error in &node.errors {
+                    write!(f, " (error at {}", error.at)?;
+                    if error.description != ErrorDescription::default() {
+                        write!(f, ": {}", error.description)?;
+                    }
+                    write!(f, ")")?;
+                }
+                writeln!(f)?;
+                fmt_events(f, &node.events, depth + 1)?;
+            }
+            TraceEvent::Backtrack { from, to } => {
+                writeln!(f, "backtracked from {} to {}", from, to)?
+            }
+            TraceEvent::Info(s) => writeln!(f, "{}", s)?,
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_events(f, &self.events, 0)
+    }
+}
+
+/// A parser enclosing a rejected parser within an [`Explanation`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExplainFrame {
+    name: &'static str,
+    label: Option<String>,
+    location: Option<Location<'static>>,
+    start: usize,
+}
+
+impl ExplainFrame {
+    /// The name of the parser's type, such as `Or` or `Then`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The label given to the parser by [`Parser::debug`], if any.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// The location in the source code of the call to [`Parser::debug`], if any.
+    pub fn location(&self) -> Option<Location<'static>> {
+        self.location
+    }
+
+    /// The offset of the input that the parser started at.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+}
+
+/// A parser that rejected the input at the furthest position reached by a parse. See [`Explanation`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rejection {
+    parser: ExplainFrame,
+    chain: Vec<ExplainFrame>,
+    error: ErrorDescription,
+}
+
+impl Rejection {
+    /// The innermost parser that rejected the input, such as `Just` or `Filter`.
+    pub fn parser(&self) -> &ExplainFrame {
+        &self.parser
+    }
+
+    /// The parsers enclosing the rejected parser, innermost first.
+    pub fn chain(&self) -> &[ExplainFrame] {
+        &self.chain
+    }
+
+    /// The error with which the input was rejected, including the inputs that were expected and the label of the
+    /// structure being parsed.
+    ///
+    /// This is the error produced by the [site](Rejection::site) of the rejection, so that it reflects any labels given
+    /// within the site (see [`Parser::labelled`]), or by the rejected parser itself if it has no site.
+    pub fn error(&self) -> &ErrorDescription {
+        &self.error
+    }
+
+    /// The innermost enclosing parser that was labelled with [`Parser::debug`], if any.
+    pub fn site(&self) -> Option<&ExplainFrame> {
+        core::iter::once(&self.parser)
+            .chain(&self.chain)
+            .find(|frame| frame.location.is_some())
+    }
+}
+
+/// An explanation of why a parse generated errors, listing every parser that rejected the input at the furthest
+/// position that the parse reached. See [`ParseConfig::explain`].
+///
+/// Parsers labelled with [`Parser::debug`] are reported with their labels and source locations, so label the rules of
+/// your grammar to make explanations easier to read. Each rejection also describes the error that the parser produced
+/// (see [`Rejection::error`]), including the inputs that it expected at the furthest position.
+///
+/// The explanation can be printed with its [`fmt::Display`] implementation.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, ParseConfig};
+/// let sum = just::<_, _, Simple<char>>('1').then(just('+')).then(just('2')).debug("sum");
+/// let product = just('1').then(just('*')).then(just('3')).debug("product");
+/// let expr = sum.or(product).debug("expr");
+///
+/// let output = expr.parse_recovery_with("1+3", ParseConfig::new().explain());
+/// let explanation = output.explanation().unwrap();
+/// assert_eq!(explanation.offset(), 2);
+///
+/// // Only `sum` got as far as the third input
+/// let rejection = &explanation.rejections()[0];
+/// assert_eq!(explanation.rejections().len(), 1);
+/// assert_eq!(rejection.parser().name(), "Just");
+/// assert_eq!(rejection.site().unwrap().label(), Some("sum"));
+/// assert_eq!(rejection.error().expected, vec![Some("'2'".to_string())]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    offset: usize,
+    rejections: Vec<Rejection>,
+}
+
+impl Explanation {
+    /// The furthest offset of the input at which an error was generated.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The parsers that rejected the input at [`Explanation::offset`].
+    pub fn rejections(&self) -> &[Rejection] {
+        &self.rejections
+    }
+
+    pub(crate) fn from_trace(trace: &Trace) -> Option<Self> {
+        let root = trace.events.iter().find_map(|event| match event {
+            TraceEvent::Invoke(node) => Some(node),
+            _ => None,
+        })?;
+        let offset = root.errors.iter().map(|e| e.at).max()?;
+        let mut rejections = Vec::new();
+        let mut chain = Vec::new();
+        collect_rejections(root, offset, &mut chain, &mut rejections);
+        Some(Self { offset, rejections })
+    }
+}
+
+impl ExplainFrame {
+    fn new(node: &TraceNode) -> Self {
+        Self {
+            name: node.name,
+            label: node.label.clone(),
+            location: node.location,
+            start: node.start,
+        }
+    }
+}
+
+impl fmt::Display for ExplainFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(label) = &self.label {
+            write!(f, "{} ", label)?;
+        }
+        write!(f, "{}", self.name)?;
+        if let Some(location) = &self.location {
+            write!(f, " (line {} in {})", location.line(), location.file())?;
+        }
+        write!(f, " at {}", self.start)
+    }
+}
+
+// Returns `true` if a parser within `node` rejected the input at `offset`. Each frame of the chain is paired with the
+// error that its parser produced at `offset`, if any.
+fn collect_rejections<'a>(
+    node: &'a TraceNode,
+    offset: usize,
+    chain: &mut Vec<(ExplainFrame, Option<&'a ErrorDescription>)>,
+    rejections: &mut Vec<Rejection>,
+) -> bool {
+    let error = node.errors.iter().rev().find(|e| e.at == offset);
+    chain.push((ExplainFrame::new(node), error.map(|e| &e.description)));
+    let mut found = false;
+    for event in &node.events {
+        if let TraceEvent::Invoke(child) = event {
+            found |= collect_rejections(child, offset, chain, rejections);
+        }
+    }
+    let (parser, error) = chain.pop().unwrap();
+
+    // A parser only rejects the input itself if none of the parsers within it did
+    let rejected =
+        node.outcome == TraceOutcome::Failure && node.errors.last().map(|e| e.at) == Some(offset);
+    if rejected && !found {
+        // The error of the enclosing site, if any, carries the labels given to the parsers within it
+        let site_error = core::iter::once(&(parser.clone(), error))
+            .chain(chain.iter().rev())
+            .find(|(frame, _)| frame.location.is_some())
+            .and_then(|(_, error)| *error);
+        let rejection = Rejection {
+            error: site_error.or(error).cloned().unwrap_or_default(),
+            parser,
+            chain: chain.iter().rev().map(|(frame, _)| frame.clone()).collect(),
+        };
+        // The same parser may be invoked at the same position many times due to backtracking
+        if !rejections.contains(&rejection) {
+            rejections.push(rejection);
+        }
+    }
+    found || rejected
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} parser(s) rejected the input at offset {}:",
+            self.rejections.len(),
+            self.offset
+        )?;
+        for rejection in &self.rejections {
+            write!(f, "    {}", rejection.parser)?;
+            if rejection.error != ErrorDescription::default() {
+                write!(f, ": {}", rejection.error)?;
+            }
+            writeln!(f)?;
+            for frame in rejection.chain.iter().filter(|frame| frame.label.is_some()) {
+                writeln!(f, "        in {}", frame)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// The name of a parser type, without its module path or generic parameters.
+pub(crate) fn short_type_name<P: ?Sized>() -> &'static str {
+    let name = core::any::type_name::<P>();
+    let name = &name[..name.find(['<', '(']).unwrap_or(name.len())];
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Statistics about a single parser site within a [`Profile`].
+///
+/// A site is a call to [`Parser::debug`]: label the rules of your grammar with it to include them in the profile.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfileSite {
+    label: String,
+    location: Location<'static>,
+    calls: usize,
+    successes: usize,
+    failures: usize,
+    reparsed: usize,
+    time: Duration,
+    self_time: Duration,
+}
+
+impl ProfileSite {
+    /// The label given to the parser by [`Parser::debug`].
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// The location in the source code of the call to [`Parser::debug`].
+    pub fn location(&self) -> Location<'static> {
+        self.location
+    }
+
+    /// The number of times that the parser was invoked.
+    pub fn calls(&self) -> usize {
+        self.calls
+    }
+
+    /// The number of invocations of the parser that succeeded (including those that recovered from errors).
+    pub fn successes(&self) -> usize {
+        self.successes
+    }
+
+    /// The number of invocations of the parser that failed.
+    pub fn failures(&self) -> usize {
+        self.failures
+    }
+
+    /// The number of inputs that the parser consumed when invoked at a position in the input that it had already
+    /// been invoked at, usually because an enclosing parser backtracked.
+    pub fn reparsed(&self) -> usize {
+        self.reparsed
+    }
+
+    /// The total time spent in the parser, including in other sites nested within it.
+    ///
+    /// Time is only measured when the `std` feature is enabled.
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    /// The time spent in the parser, excluding time spent in other sites nested within it.
+    pub fn self_time(&self) -> Duration {
+        self.self_time
+    }
+}
+
+/// A report of the time spent in each parser site during a parse, sorted by [`ProfileSite::self_time`] from highest
+/// to lowest. See [`ParseConfig::profile`].
+///
+/// The report can be printed as a table with its [`fmt::Display`] implementation.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, ParseConfig};
+/// let digit = filter::<_, _, Simple<char>>(|c: &char| c.is_ascii_digit()).debug("digit");
+/// let number = digit.repeated().at_least(1).debug("number");
+/// let sum = number.clone().then_ignore(just('+')).chain(number.clone()).debug("sum");
+/// let expr = sum.or(number).debug("expr");
+///
+/// let output = expr.parse_recovery_with("123", ParseConfig::new().profile());
+/// let profile = output.profile().unwrap();
+///
+/// let number = profile.sites().iter().find(|s| s.label() == "number").unwrap();
+/// assert_eq!(number.calls(), 2);
+/// // `sum` failed, so `number` parsed "123" twice
+/// assert_eq!(number.reparsed(), 3);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Profile {
+    sites: Vec<ProfileSite>,
+}
+
+impl Profile {
+    /// The parser sites that were invoked during the parse.
+    pub fn sites(&self) -> &[ProfileSite] {
+        &self.sites
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>12} {:>12} {:>8} {:>8} {:>8} {:>8}  site",
+            "self time", "total time", "calls", "ok", "failed", "reparsed"
+        )?;
+        for site in &self.sites {
+            writeln!(
+                f,
+                "{:>12} {:>12} {:>8} {:>8} {:>8} {:>8}  {} (line {} in {})",
+                format!("{:.2?}", site.self_time),
+                format!("{:.2?}", site.time),
+                site.calls,
+                site.successes,
+                site.failures,
+                site.reparsed,
+                site.label,
+                site.location.line(),
+                site.location.file(),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct Profiler {
+    sites: Vec<ProfileSite>,
+    indices: BTreeMap<(&'static str, u32, u32), usize>,
+    // The offsets at which each site has been invoked
+    visited: BTreeSet<(usize, usize)>,
+    // The time spent in nested sites, for each invocation currently running
+    nested: Vec<Duration>,
+}
+
+impl Profiler {
+    fn record(&mut self, info: &ParserInfo, offsets: Range<usize>, ok: bool, time: Duration) {
+        let location = info.location;
+        let key = (location.file(), location.line(), location.column());
+        let idx = match self.indices.get(&key) {
+            Some(idx) => *idx,
+            None => {
+                self.sites.push(ProfileSite {
+                    label: info.display.to_string(),
+                    location,
+                    calls: 0,
+                    successes: 0,
+                    failures: 0,
+                    reparsed: 0,
+                    time: Duration::ZERO,
+                    self_time: Duration::ZERO,
+                });
+                self.indices.insert(key, self.sites.len() - 1);
+                self.sites.len() - 1
+            }
+        };
+        let nested = self.nested.pop().unwrap_or_default();
+        let site = &mut self.sites[idx];
+        site.calls += 1;
+        if ok {
+            site.successes += 1;
+        } else {
+            site.failures += 1;
+        }
+        if !self.visited.insert((idx, offsets.start)) {
+            site.reparsed += offsets.end.saturating_sub(offsets.start);
+        }
+        site.time += time;
+        site.self_time += time.saturating_sub(nested);
+        if let Some(parent) = self.nested.last_mut() {
+            *parent += time;
+        }
+    }
+
+    fn finish(mut self) -> Profile {
+        self.sites
+            .sort_by(|a, b| b.self_time.cmp(&a.self_time).then(b.calls.cmp(&a.calls)));
+        Profile { sites: self.sites }
+    }
+}
+
+/// A verbose debugger that records a [`Trace`] of every parser invocation, a [`Profile`] of each parser site, or
+/// both.
 pub struct Verbose {
-    // TODO: Don't use `Result`, that's silly
-    events: Vec<Result<ParseEvent, (ParserInfo, Self)>>,
+    info: Option<ParserInfo>,
+    events: Vec<TraceEvent>,
+    trace: bool,
+    profiler: Option<Profiler>,
 }
 
 impl Verbose {
-    pub(crate) fn new() -> Self {
-        Self { events: Vec::new() }
+    pub(crate) fn new(trace: bool, profile: bool) -> Self {
+        Self {
+            info: None,
+            events: Vec::new(),
+            trace,
+            profiler: if profile {
+                Some(Profiler::default())
+            } else {
+                None
+            },
+        }
+    }
+
+    pub(crate) fn finish(self) -> (Option<Trace>, Option<Profile>) {
+        (
+            if self.trace {
+                Some(Trace {
+                    events: self.events,
+                })
+            } else {
+                None
+            },
+            self.profiler.map(Profiler::finish),
+        )
     }
 
+    /// Print the parsers labelled with [`Parser::debug`] that were entered, along with any debugging information they
+    /// emitted. The full trace is available through [`ParseConfig::trace`].
     #[allow(unused_variables)]
-    fn print_inner(&self, depth: usize) {
+    fn print_inner(events: &[TraceEvent], depth: usize) {
         // a no-op on no_std!
         #[cfg(feature = "std")]
-        for event in &self.events {
-            for _ in 0..depth * 4 {
-                print!(" ");
-            }
+        for event in events {
             match event {
-                Ok(ParseEvent::Info(s)) => println!("{}", s),
-                Err((info, scope)) => {
-                    println!(
-                        "Entered {} at line {} in {}",
-                        info.display,
-                        info.location.line(),
-                        info.location.file()
-                    );
-                    scope.print_inner(depth + 1);
-                }
+                TraceEvent::Invoke(node) => match (&node.label, &node.location) {
+                    (Some(label), Some(location)) => {
+                        println!(
+                            "{:width$}Entered {} at line {} in {}",
+                            "",
+                            label,
+                            location.line(),
+                            location.file(),
+                            width = depth * 4
+                        );
+                        Self::print_inner(&node.events, depth + 1);
+                    }
+                    // Parsers without a site are transparent
+                    _ => Self::print_inner(&node.events, depth),
+                },
+                TraceEvent::Backtrack { .. } => {}
+                TraceEvent::Info(s) => println!("{:width$}{}", "", s, width = depth * 4),
             }
         }
     }
 
-    pub(crate) fn print(&self) {
-        self.print_inner(0)
+    pub(crate) fn print(self) {
+        if let (Some(trace), _) = self.finish() {
+            Self::print_inner(&trace.events, 0)
+        }
     }
 }
 
@@ -102,14 +864,17 @@ impl Debugger for Verbose {
         info: Info,
         f: F,
     ) -> R {
-        let mut verbose = Verbose { events: Vec::new() };
-        let res = f(&mut verbose);
-        self.events.push(Err((info(), verbose)));
-        res
+        // The scope belongs to the invocation of the parser that is currently running
+        self.info = Some(info());
+        f(self)
     }
 
     fn emit_with<F: FnOnce() -> ParseEvent>(&mut self, f: F) {
-        self.events.push(Ok(f()));
+        if self.trace {
+            match f() {
+                ParseEvent::Info(s) => self.events.push(TraceEvent::Info(s)),
+            }
+        }
     }
 
     fn invoke<I: Clone, O, P: Parser<I, O> + ?Sized>(
@@ -117,7 +882,71 @@ impl Debugger for Verbose {
         parser: &P,
         stream: &mut StreamOf<I, P::Error>,
     ) -> PResult<I, O, P::Error> {
-        parser.parse_inner_verbose(self, stream)
+        if let Err(err) = stream.step() {
+            return (Vec::new(), Err(err));
+        }
+        let start = stream.offset();
+        if let Some(TraceEvent::Invoke(prev)) = self.events.last() {
+            if prev.end > start {
+                let from = prev.end;
+                self.events.push(TraceEvent::Backtrack { from, to: start });
+            }
+        }
+        if let Some(profiler) = &mut self.profiler {
+            profiler.nested.push(Duration::ZERO);
+        }
+        #[cfg(feature = "std")]
+        let timer = self.profiler.as_ref().map(|_| std::time::Instant::now());
+
+        let info = self.info.take();
+        let events = core::mem::take(&mut self.events);
+        let (errors, res) = parser.parse_inner_verbose(self, stream);
+        let info = core::mem::replace(&mut self.info, info);
+        let events = core::mem::replace(&mut self.events, events);
+
+        if let Some(profiler) = &mut self.profiler {
+            #[cfg(feature = "std")]
+            let time = timer.map_or(Duration::ZERO, |timer| timer.elapsed());
+            #[cfg(not(feature = "std"))]
+            let time = Duration::ZERO;
+            match &info {
+                Some(info) => profiler.record(info, start..stream.offset(), res.is_ok(), time),
+                // Parsers without a site are transparent, so time spent in sites nested within them is attributed to
+                // the enclosing site
+                None => {
+                    let nested = profiler.nested.pop().unwrap_or_default();
+                    if let Some(parent) = profiler.nested.last_mut() {
+                        *parent += nested;
+                    }
+                }
+            }
+        }
+
+        if self.trace {
+            self.events.push(TraceEvent::Invoke(TraceNode {
+                name: short_type_name::<P>(),
+                label: info.as_ref().map(|info| info.display.to_string()),
+                location: info.map(|info| info.location),
+                start,
+                end: stream.offset(),
+                outcome: match &res {
+                    Ok(_) if errors.is_empty() => TraceOutcome::Success,
+                    Ok(_) => TraceOutcome::Recovered,
+                    Err(_) => TraceOutcome::Failure,
+                },
+                errors: errors
+                    .iter()
+                    .chain(res.as_ref().err())
+                    .map(|e| TraceError {
+                        at: e.at,
+                        description: e.error.describe(stream.describe_input),
+                    })
+                    .collect(),
+                events,
+            }));
+        }
+
+        (errors, res)
     }
 }
 
@@ -149,6 +978,123 @@ impl Debugger for Silent {
         parser: &P,
         stream: &mut StreamOf<I, P::Error>,
     ) -> PResult<I, O, P::Error> {
+        if let Err(err) = stream.step() {
+            return (Vec::new(), Err(err));
+        }
         parser.parse_inner_silent(self, stream)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Simple, ParseConfig};
+
+    #[test]
+    fn profile() {
+        let digit = filter::<_, _, Simple<char>>(|c: &char| c.is_ascii_digit()).debug("digit");
+        let number = digit.repeated().at_least(1).debug("number");
+        let sum = number
+            .clone()
+            .then_ignore(just('+'))
+            .chain(number.clone())
+            .debug("sum");
+        let expr = sum.or(number).debug("expr");
+
+        let output = expr.parse_recovery_with("123", ParseConfig::new().profile());
+        let profile = output.profile().unwrap();
+        let site = |label| profile.sites().iter().find(|s| s.label() == label).unwrap();
+        let counts = |label| {
+            let site = site(label);
+            (
+                site.calls(),
+                site.successes(),
+                site.failures(),
+                site.reparsed(),
+            )
+        };
+
+        assert_eq!(profile.sites().len(), 4);
+        assert_eq!(counts("expr"), (1, 1, 0, 0));
+        assert_eq!(counts("sum"), (1, 0, 1, 0));
+        assert_eq!(counts("number"), (2, 2, 0, 3));
+        // Each attempt at `number` tries a fourth digit at the end of the input
+        assert_eq!(counts("digit"), (8, 6, 2, 3));
+
+        // Sites are sorted by the time spent in them, excluding nested sites, which accounts for all of the time spent
+        // in the outermost site
+        let sites = profile.sites();
+        assert!(sites
+            .windows(2)
+            .all(|w| w[0].self_time() >= w[1].self_time()));
+        assert!(sites.iter().all(|s| s.self_time() <= s.time()));
+        assert!(site("expr").time() >= site("sum").time());
+        assert_eq!(
+            sites.iter().map(|s| s.self_time()).sum::<Duration>(),
+            site("expr").time()
+        );
+
+        assert!(profile
+            .to_string()
+            .starts_with("   self time   total time    calls       ok   failed reparsed  site\n"));
+    }
+
+    #[test]
+    fn explanation() {
+        let sum = just::<_, _, Simple<char>>('1')
+            .then(just('+'))
+            .then(just('2').or(just('3').then(just('0')).to('3')))
+            .labelled("sum")
+            .debug("sum");
+        let product = just('1').then(just('*')).then(just('3')).debug("product");
+        let expr = sum.or(product).debug("expr");
+
+        let output = expr.parse_recovery_with("1+4", ParseConfig::new().explain());
+        let explanation = output.explanation().unwrap();
+        assert_eq!(explanation.offset(), 2);
+
+        // Both alternatives of the last part of `sum` rejected the input
+        let rejections = explanation.rejections();
+        assert_eq!(rejections.len(), 2);
+        for rejection in rejections {
+            assert_eq!(rejection.parser().name(), "Just");
+            assert_eq!(rejection.parser().start(), 2);
+            assert_eq!(rejection.site().unwrap().label(), Some("sum"));
+            let error = rejection.error();
+            assert_eq!(error.message.as_deref(), Some("found '4'"));
+            assert_eq!(
+                error.expected,
+                vec![Some("'2'".to_string()), Some("'3'".to_string())]
+            );
+            assert_eq!(error.label.as_deref(), Some("sum"));
+        }
+
+        let text = explanation.to_string();
+        assert!(text.starts_with("2 parser(s) rejected the input at offset 2:\n"));
+        assert!(text.contains("Just at 2: found '4', expected '2' or '3', in sum\n"));
+    }
+
+    #[test]
+    fn trace_without_describing_tokens() {
+        // Tokens need not implement `Debug` to be traced
+        #[derive(Clone, PartialEq, Eq, Hash)]
+        enum Token {
+            A,
+            B,
+        }
+
+        let parser = just::<_, _, Simple<Token>>(Token::A).labelled("a");
+        let output = parser.parse_recovery_with(vec![Token::B], ParseConfig::new().trace());
+        let node = match &output.trace().unwrap().events()[0] {
+            TraceEvent::Invoke(node) => node,
+            _ => panic!("expected an invocation"),
+        };
+        let description = node.errors()[0].description();
+        assert_eq!(
+            description.message.as_deref(),
+            Some("found an unexpected input")
+        );
+        assert!(description.expected.is_empty());
+        assert_eq!(description.label.as_deref(), Some("a"));
+    }
+}
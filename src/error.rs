@@ -117,6 +117,97 @@ pub trait Error<I>: Sized {
         Self::expected_input_found(span, Some(Some(expected)), found)
     }
 
+    /// Create a new error describing a closing delimiter that does not match the innermost open delimiter.
+    ///
+    /// Provided to this function is the span of the open delimiter, the delimiter itself, the span of the mismatched
+    /// closing delimiter, the closing delimiter that was expected, and the closing delimiter that was found instead.
+    ///
+    /// The default implementation of this function uses [`Error::unclosed_delimiter`].
+    fn mismatched_delimiter(
+        unclosed_span: Self::Span,
+        unclosed: I,
+        span: Self::Span,
+        expected: I,
+        found: I,
+    ) -> Self {
+        Self::unclosed_delimiter(unclosed_span, unclosed, span, expected, Some(found))
+    }
+
+    /// Create a new error describing a repeating parser that stopped because an iteration succeeded without consuming
+    /// any input. See [`ParseConfig::on_stalled_loop`].
+    ///
+    /// Provided to this function is the (empty) span at which the repetition stalled and the source location of the
+    /// repeating parser.
+    ///
+    /// The default implementation of this function uses [`Error::expected_input_found`].
+    fn stalled_loop(span: Self::Span, location: Location<'static>) -> Self {
+        #![allow(unused_variables)]
+        Self::expected_input_found(span, None, None)
+    }
+
+    /// Create a new error describing a [`Recursive`](crate::recursive::Recursive) parser that was re-entered without
+    /// consuming any input. See [`ParseConfig::on_left_recursion`].
+    ///
+    /// Provided to this function is the (empty) span at which the parser was re-entered and the source location at
+    /// which the recursive parser was declared.
+    ///
+    /// The default implementation of this function uses [`Error::expected_input_found`].
+    fn left_recursion(span: Self::Span, location: Location<'static>) -> Self {
+        #![allow(unused_variables)]
+        Self::expected_input_found(span, None, None)
+    }
+
+    /// Create a new error describing a parse that was abandoned because it exceeded a limit. See
+    /// [`ParseConfig::max_depth`] and [`ParseConfig::fuel`].
+    ///
+    /// Provided to this function is the (empty) span at which the limit was exceeded and the limit itself.
+    ///
+    /// The default implementation of this function uses [`Error::expected_input_found`].
+    fn limit_exceeded(span: Self::Span, limit: Limit) -> Self {
+        #![allow(unused_variables)]
+        Self::expected_input_found(span, None, None)
+    }
+
+    /// Create a new error describing a parse that was abandoned because it was cancelled. See
+    /// [`ParseConfig::cancel_on`].
+    ///
+    /// Provided to this function is the (empty) span at which the parse was cancelled.
+    ///
+    /// The default implementation of this function uses [`Error::expected_input_found`].
+    fn cancelled(span: Self::Span) -> Self {
+        Self::expected_input_found(span, None, None)
+    }
+
+    /// If this error describes a delimiter that was not correctly closed (see [`Error::unclosed_delimiter`]), get the
+    /// span of the unclosed delimiter and the delimiter itself.
+    ///
+    /// This is used to report the delimiters involved in an incomplete parse (see
+    /// [`ParseOutput::unclosed_delimiters`]). The default implementation of this function returns `None`.
+    fn unclosed(&self) -> Option<(&Self::Span, &I)> {
+        None
+    }
+
+    /// Returns `true` if this error was caused by reaching the end of the input, such that more input might have
+    /// avoided it.
+    ///
+    /// This is used to decide whether a parse is incomplete (see [`ParseOutput::is_incomplete`]). The default
+    /// implementation of this function returns `false`.
+    fn is_unexpected_end(&self) -> bool {
+        false
+    }
+
+    /// Describe this error for debugging output, such as a [`Trace`] or an [`Explanation`].
+    ///
+    /// Provided to this function is a function that renders an input as text, if the stream being parsed has one (see
+    /// [`Stream::describe_tokens`]). Inputs should be left out of the description if it does not.
+    ///
+    /// The default implementation of this function returns an empty description, so that only the position of the
+    /// error is reported.
+    fn describe(&self, input: Option<fn(&I) -> String>) -> ErrorDescription {
+        #![allow(unused_variables)]
+        ErrorDescription::default()
+    }
+
     /// Indicate that the error occurred while parsing a particular syntactic structure.
     ///
     /// How the error handles this information is up to it. It can append it to a list of structures to get a sort of
@@ -162,10 +253,26 @@ pub enum SimpleReason<I, S> {
         /// The unclosed delimiter.
         delimiter: I,
     },
+    /// A closing delimiter was found that does not match the innermost open delimiter.
+    Mismatched {
+        /// The span of the open delimiter.
+        span: S,
+        /// The open delimiter.
+        delimiter: I,
+    },
     /// An error with a custom message occurred.
     Custom(String),
 }
 
+impl<I, S: Shift> Shift for SimpleReason<I, S> {
+    fn shift(&mut self, by: isize) {
+        match self {
+            Self::Unclosed { span, .. } | Self::Mismatched { span, .. } => span.shift(by),
+            Self::Unexpected | Self::Custom(_) => {}
+        }
+    }
+}
+
 impl<I: fmt::Display, S: fmt::Display> fmt::Display for SimpleReason<I, S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         const DEFAULT_DISPLAY_UNEXPECTED: &str = "unexpected input";
@@ -175,6 +282,13 @@ impl<I: fmt::Display, S: fmt::Display> fmt::Display for SimpleReason<I, S> {
             Self::Unclosed { span, delimiter } => {
                 write!(f, "unclosed delimiter ({}) in {}", span, delimiter)
             }
+            Self::Mismatched { span, delimiter } => {
+                write!(
+                    f,
+                    "mismatched closing delimiter for ({}) in {}",
+                    span, delimiter
+                )
+            }
             Self::Custom(string) => write!(f, "error {}", string),
         }
     }
@@ -242,6 +356,10 @@ impl<I: Hash + Eq, S: Clone> Simple<I, S> {
                     span,
                     delimiter: f(delimiter),
                 },
+                SimpleReason::Mismatched { span, delimiter } => SimpleReason::Mismatched {
+                    span,
+                    delimiter: f(delimiter),
+                },
                 SimpleReason::Unexpected => SimpleReason::Unexpected,
                 SimpleReason::Custom(msg) => SimpleReason::Custom(msg),
             },
@@ -289,6 +407,99 @@ impl<I: Hash + Eq, S: Span + Clone + fmt::Debug> Error<I> for Simple<I, S> {
         }
     }
 
+    fn mismatched_delimiter(
+        unclosed_span: Self::Span,
+        delimiter: I,
+        span: Self::Span,
+        expected: I,
+        found: I,
+    ) -> Self {
+        Self {
+            span,
+            reason: SimpleReason::Mismatched {
+                span: unclosed_span,
+                delimiter,
+            },
+            expected: core::iter::once(Some(expected)).collect(),
+            found: Some(found),
+            label: None,
+        }
+    }
+
+    fn stalled_loop(span: Self::Span, location: Location<'static>) -> Self {
+        Self::custom(
+            span,
+            format!("repeating parser at {} consumed no input", location),
+        )
+    }
+
+    fn limit_exceeded(span: Self::Span, limit: Limit) -> Self {
+        Self::custom(span, format!("parse exceeded the {}", limit))
+    }
+
+    fn cancelled(span: Self::Span) -> Self {
+        Self::custom(span, "parse was cancelled")
+    }
+
+    fn unclosed(&self) -> Option<(&Self::Span, &I)> {
+        match &self.reason {
+            SimpleReason::Unclosed { span, delimiter } => Some((span, delimiter)),
+            _ => None,
+        }
+    }
+
+    fn is_unexpected_end(&self) -> bool {
+        self.found.is_none()
+            && matches!(
+                self.reason,
+                SimpleReason::Unexpected | SimpleReason::Unclosed { .. }
+            )
+    }
+
+    fn describe(&self, input: Option<fn(&I) -> String>) -> ErrorDescription {
+        // Without a way to render inputs, only say what kind of error this is
+        let with_input = |what: &str, i: &I| match input {
+            Some(input) => format!("{} {}", what, input(i)),
+            None => what.to_string(),
+        };
+        let message = match &self.reason {
+            SimpleReason::Unexpected => match (&self.found, input) {
+                (Some(found), Some(_)) => with_input("found", found),
+                (Some(_), None) => "found an unexpected input".to_string(),
+                (None, _) => "found end of input".to_string(),
+            },
+            SimpleReason::Unclosed { delimiter, .. } => with_input("unclosed delimiter", delimiter),
+            SimpleReason::Mismatched { delimiter, .. } => {
+                with_input("mismatched delimiter", delimiter)
+            }
+            SimpleReason::Custom(msg) => msg.clone(),
+        };
+        let mut expected = match input {
+            Some(input) => self
+                .expected
+                .iter()
+                .map(|e| e.as_ref().map(input))
+                .collect::<Vec<_>>(),
+            None => Vec::new(),
+        };
+        expected.sort();
+        ErrorDescription {
+            message: Some(message),
+            expected,
+            label: self.label.map(ToString::to_string),
+        }
+    }
+
+    fn left_recursion(span: Self::Span, location: Location<'static>) -> Self {
+        Self::custom(
+            span,
+            format!(
+                "recursive parser declared at {} is left-recursive",
+                location
+            ),
+        )
+    }
+
     fn with_label(mut self, label: Self::Label) -> Self {
         self.label.get_or_insert(label);
         self
@@ -297,8 +508,8 @@ impl<I: Hash + Eq, S: Span + Clone + fmt::Debug> Error<I> for Simple<I, S> {
     fn merge(mut self, other: Self) -> Self {
         // TODO: Assert that `self.span == other.span` here?
         self.reason = match (&self.reason, &other.reason) {
-            (SimpleReason::Unclosed { .. }, _) => self.reason,
-            (_, SimpleReason::Unclosed { .. }) => other.reason,
+            (SimpleReason::Unclosed { .. } | SimpleReason::Mismatched { .. }, _) => self.reason,
+            (_, SimpleReason::Unclosed { .. } | SimpleReason::Mismatched { .. }) => other.reason,
             _ => self.reason,
         };
         for expected in other.expected {
@@ -319,6 +530,13 @@ impl<I: Hash + Eq, S: PartialEq> PartialEq for Simple<I, S> {
 
 impl<I: Hash + Eq, S: Eq> Eq for Simple<I, S> {}
 
+impl<I: Hash + Eq, S: Shift> Shift for Simple<I, S> {
+    fn shift(&mut self, by: isize) {
+        self.span.shift(by);
+        self.reason.shift(by);
+    }
+}
+
 impl<I: fmt::Display + Hash + Eq, S: Span> fmt::Display for Simple<I, S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // TODO: Take `self.reason` into account
@@ -371,6 +589,7 @@ impl<I: fmt::Debug + fmt::Display + Hash + Eq, S: Span + fmt::Display + fmt::Deb
 pub struct Cheap<I, S = Range<usize>> {
     span: S,
     label: Option<&'static str>,
+    found_end: bool,
     phantom: PhantomData<I>,
 }
 
@@ -386,6 +605,12 @@ impl<I, S: Clone> Cheap<I, S> {
     }
 }
 
+impl<I, S: Shift> Shift for Cheap<I, S> {
+    fn shift(&mut self, by: isize) {
+        self.span.shift(by);
+    }
+}
+
 impl<I, S: Span + Clone + fmt::Debug> Error<I> for Cheap<I, S> {
     type Span = S;
     type Label = &'static str;
@@ -393,11 +618,12 @@ impl<I, S: Span + Clone + fmt::Debug> Error<I> for Cheap<I, S> {
     fn expected_input_found<Iter: IntoIterator<Item = Option<I>>>(
         span: Self::Span,
         _: Iter,
-        _: Option<I>,
+        found: Option<I>,
     ) -> Self {
         Self {
             span,
             label: None,
+            found_end: found.is_none(),
             phantom: PhantomData,
         }
     }
@@ -407,6 +633,17 @@ impl<I, S: Span + Clone + fmt::Debug> Error<I> for Cheap<I, S> {
         self
     }
 
+    fn is_unexpected_end(&self) -> bool {
+        self.found_end
+    }
+
+    fn describe(&self, _: Option<fn(&I) -> String>) -> ErrorDescription {
+        ErrorDescription {
+            label: self.label.map(ToString::to_string),
+            ..ErrorDescription::default()
+        }
+    }
+
     fn merge(self, _: Self) -> Self {
         self
     }
@@ -414,9 +651,13 @@ impl<I, S: Span + Clone + fmt::Debug> Error<I> for Cheap<I, S> {
 
 /// An internal type used to facilitate error prioritisation. You shouldn't need to interact with this type during
 /// normal use of the crate.
-pub struct Located<I, E> {
+pub struct Located<I, E: Error<I>> {
     pub(crate) at: usize,
     pub(crate) error: E,
+    pub(crate) region: Option<Region>,
+    /// The labels of the parsers that the error passed through, innermost first. Only recorded while looking for
+    /// completions (see [`Parser::expected_at`]).
+    pub(crate) contexts: Vec<E::Label>,
     pub(crate) phantom: PhantomData<I>,
 }
 
@@ -426,6 +667,8 @@ impl<I, E: Error<I>> Located<I, E> {
         Self {
             at,
             error,
+            region: None,
+            contexts: Vec::new(),
             phantom: PhantomData,
         }
     }
@@ -439,21 +682,36 @@ impl<I, E: Error<I>> Located<I, E> {
         match self.at.cmp(&other.at) {
             Ordering::Greater => self,
             Ordering::Less => other,
-            Ordering::Equal => Self {
-                error: self.error.merge(other.error),
-                ..self
-            },
+            Ordering::Equal => {
+                let mut contexts = self.contexts;
+                contexts.extend(other.contexts);
+                Self {
+                    error: self.error.merge(other.error),
+                    contexts,
+                    ..self
+                }
+            }
         }
     }
 
     /// Map the error with the given function.
-    pub fn map<U, F: FnOnce(E) -> U>(self, f: F) -> Located<I, U> {
+    pub fn map<U: Error<I>, F: FnOnce(E) -> U>(self, f: F) -> Located<I, U> {
         Located {
             at: self.at,
             error: f(self.error),
+            region: self.region,
+            contexts: Vec::new(),
             phantom: PhantomData,
         }
     }
+
+    /// Map the error with the given function, keeping its contexts.
+    pub(crate) fn map_error<F: FnOnce(E) -> E>(self, f: F) -> Self {
+        Self {
+            error: f(self.error),
+            ..self
+        }
+    }
 }
 
 // Merge two alternative errors
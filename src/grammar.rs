@@ -0,0 +1,2096 @@
+//! Types that describe the structure of parsers, allowing grammars to be inspected after they have been built.
+//!
+//! *“Forty-two,” said Deep Thought, with infinite majesty and calm.*
+//!
+//! Every parser in this crate describes itself via [`Parser::describe`], producing a tree of [`Node`]s. Use
+//! [`Grammar::of`] to describe a whole parser, including any [`Recursive`](recursive::Recursive) parsers within it
+//! (which are described once each, as [`Rule`]s, so that cycles can be represented).
+//!
+//! Parsers that you implement yourself are described as [`Kind::Opaque`] unless you override [`Parser::describe`].
+
+use super::*;
+
+use alloc::{format, string::ToString};
+
+/// The kind of a [`Node`], describing what it matches.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Kind<I> {
+    /// Exactly the given sequence of inputs (such as [`just`] or [`seq`]).
+    Just(Vec<I>),
+    /// Any one of the given inputs (see [`one_of`]).
+    OneOf(Vec<I>),
+    /// Any single input other than the given inputs (see [`none_of`]).
+    NoneOf(Vec<I>),
+    /// A single input that satisfies an opaque condition (such as [`filter`], [`filter_map`] or [`any`]).
+    Filter,
+    /// A well-known class of inputs with the given name, such as an identifier (see [`text::ident`]).
+    Terminal(&'static str),
+    /// The end of the input (see [`end`]).
+    End,
+    /// Nothing at all (see [`empty`]).
+    Empty,
+    /// A parser whose structure is unknown, such as [`custom`] or one that you've implemented yourself.
+    Opaque,
+    /// Each of the given patterns, one after another.
+    Seq(Vec<Node<I>>),
+    /// The first of the given patterns that matches.
+    Choice(Vec<Node<I>>),
+    /// The given pattern, or nothing (see [`Parser::or_not`]).
+    Optional(Box<Node<I>>),
+    /// The given pattern, repeated (see [`Parser::repeated`] and [`Parser::separated_by`]).
+    Repeat {
+        /// The pattern being repeated.
+        item: Box<Node<I>>,
+        /// The pattern separating each item, if any.
+        separator: Option<Box<Node<I>>>,
+        /// The minimum number of items.
+        at_least: usize,
+        /// The maximum number of items, if any.
+        at_most: Option<usize>,
+        /// Whether a separator may appear before the first item.
+        allow_leading: bool,
+        /// Whether a separator may appear after the last item.
+        allow_trailing: bool,
+    },
+    /// The given pattern, with its output or errors transformed in some way that does not affect what it matches
+    /// (such as [`Parser::map`] or [`Parser::labelled`]).
+    Wrap(Box<Node<I>>),
+    /// The given pattern, with its output checked by a function that may reject it (see [`Parser::try_map`] and
+    /// [`text::keyword`]).
+    TryMap(Box<Node<I>>),
+    /// The given pattern, without consuming any input (see [`Parser::rewind`]).
+    Lookahead(Box<Node<I>>),
+    /// A single input that does not begin the given pattern (see [`Parser::not`]).
+    Not(Box<Node<I>>),
+    /// Any inputs, up to and including the given pattern (see [`take_until`]).
+    TakeUntil(Box<Node<I>>),
+    /// The given pattern, followed by a pattern that depends on its output (see [`Parser::then_with`]).
+    ThenWith(Box<Node<I>>),
+    /// A reference to the [`Rule`] at the given index of the [`Grammar`], created by a
+    /// [`Recursive`](recursive::Recursive) parser.
+    Rule(usize),
+}
+
+/// A description of the structure of a parser. See [`Parser::describe`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Node<I> {
+    name: &'static str,
+    kind: Kind<I>,
+    label: Option<String>,
+    location: Option<Location<'static>>,
+}
+
+impl<I> Node<I> {
+    /// Create a new node with the given name (usually the name of the function or method that created the parser,
+    /// such as `"just"` or `"then"`) and kind.
+    pub fn new(name: &'static str, kind: Kind<I>) -> Self {
+        Self {
+            name,
+            kind,
+            label: None,
+            location: None,
+        }
+    }
+
+    // A node that wraps `node` without changing what it matches.
+    pub(crate) fn wrap(name: &'static str, node: Self) -> Self {
+        Self::new(name, Kind::Wrap(Box::new(node)))
+    }
+
+    /// Give the node a label.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Give the node a location in the source code.
+    pub fn with_location(mut self, location: Location<'static>) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// The name of the function or method that created the parser, such as `"just"` or `"then"`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The kind of the node.
+    pub fn kind(&self) -> &Kind<I> {
+        &self.kind
+    }
+
+    /// The label given to the parser by [`Parser::labelled`] or [`Parser::debug`], if any.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// The location in the source code at which the parser was created, if known.
+    pub fn location(&self) -> Option<Location<'static>> {
+        self.location
+    }
+
+    /// The literal inputs matched by the node, if it is a [`Kind::Just`], [`Kind::OneOf`] or [`Kind::NoneOf`].
+    pub fn tokens(&self) -> &[I] {
+        match &self.kind {
+            Kind::Just(tokens) | Kind::OneOf(tokens) | Kind::NoneOf(tokens) => tokens,
+            _ => &[],
+        }
+    }
+
+    /// The nodes within this node, in order.
+    pub fn children(&self) -> Vec<&Node<I>> {
+        match &self.kind {
+            Kind::Seq(nodes) | Kind::Choice(nodes) => nodes.iter().collect(),
+            Kind::Repeat {
+                item, separator, ..
+            } => core::iter::once(&**item)
+                .chain(separator.as_deref())
+                .collect(),
+            Kind::Optional(node)
+            | Kind::Wrap(node)
+            | Kind::TryMap(node)
+            | Kind::Lookahead(node)
+            | Kind::Not(node)
+            | Kind::TakeUntil(node)
+            | Kind::ThenWith(node) => vec![&**node],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Simplify the node, removing [`Kind::Wrap`] nodes and merging nested sequences and choices.
+    ///
+    /// Labels are preserved: a wrapper with a label or location is only removed if the node within it can take them
+    /// instead, and nested sequences and choices with labels are not merged (the locations of those without labels are
+    /// discarded when they are merged).
+    pub fn simplify(self) -> Self {
+        let Node {
+            name,
+            kind,
+            label,
+            location,
+        } = self;
+        let kind = match kind {
+            Kind::Wrap(node) => {
+                let node = node.simplify();
+                if (label.is_none() || node.label.is_none())
+                    && (location.is_none() || node.location.is_none())
+                {
+                    return Node {
+                        label: label.or(node.label),
+                        location: location.or(node.location),
+                        ..node
+                    };
+                }
+                Kind::Wrap(Box::new(node))
+            }
+            Kind::Seq(nodes) => Kind::Seq(Self::merge(nodes, true)),
+            Kind::Choice(nodes) => Kind::Choice(Self::merge(nodes, false)),
+            Kind::Repeat {
+                item,
+                separator,
+                at_least,
+                at_most,
+                allow_leading,
+                allow_trailing,
+            } => Kind::Repeat {
+                item: Box::new(item.simplify()),
+                separator: separator.map(|sep| Box::new(sep.simplify())),
+                at_least,
+                at_most,
+                allow_leading,
+                allow_trailing,
+            },
+            Kind::Optional(node) => Kind::Optional(Box::new(node.simplify())),
+            Kind::TryMap(node) => Kind::TryMap(Box::new(node.simplify())),
+            Kind::Lookahead(node) => Kind::Lookahead(Box::new(node.simplify())),
+            Kind::Not(node) => Kind::Not(Box::new(node.simplify())),
+            Kind::TakeUntil(node) => Kind::TakeUntil(Box::new(node.simplify())),
+            Kind::ThenWith(node) => Kind::ThenWith(Box::new(node.simplify())),
+            kind => kind,
+        };
+        Node {
+            name,
+            kind,
+            label,
+            location,
+        }
+    }
+
+    // Simplify the given nodes of a sequence (or choice), splicing the contents of unlabelled sequences (or choices)
+    // into them.
+    fn merge(nodes: Vec<Self>, seq: bool) -> Vec<Self> {
+        let mut merged = Vec::with_capacity(nodes.len());
+        for node in nodes.into_iter().map(Self::simplify) {
+            if node.label.is_some() {
+                merged.push(node);
+                continue;
+            }
+            let Node { name, kind, .. } = node;
+            match (seq, kind) {
+                (true, Kind::Seq(nodes)) | (false, Kind::Choice(nodes)) => merged.extend(nodes),
+                (_, kind) => merged.push(Node::new(name, kind)),
+            }
+        }
+        merged
+    }
+}
+
+/// A named pattern within a [`Grammar`], created by a [`Recursive`](recursive::Recursive) parser.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rule<I> {
+    name: Option<String>,
+    node: Option<Node<I>>,
+    location: Location<'static>,
+}
+
+impl<I> Rule<I> {
+    /// The name of the rule, if known.
+    ///
+    /// A rule takes its name from a label given to the [`Recursive`](recursive::Recursive) parser (via
+    /// [`Parser::labelled`] or [`Parser::debug`]) or, failing that, to the parser that defines it.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The location in the source code at which the [`Recursive`](recursive::Recursive) parser was declared.
+    pub fn location(&self) -> Location<'static> {
+        self.location
+    }
+
+    /// The pattern that defines the rule.
+    pub fn node(&self) -> &Node<I> {
+        self.node
+            .as_ref()
+            .expect("Rule was described before being defined")
+    }
+}
+
+/// State used while describing a parser. See [`Parser::describe`].
+pub struct Describer<I, E: Error<I>> {
+    rules: Vec<Rule<I>>,
+    ids: Vec<(*const (), usize)>,
+    label: fn(E::Label) -> String,
+    sites: Option<Vec<(Site, Arc<E::Label>)>>,
+    memoized: Option<Vec<Site>>,
+}
+
+impl<I, E: Error<I>> Describer<I, E> {
+    /// A describer that only looks for sites.
+    fn finder() -> Self {
+        fn label<L>(_: L) -> String {
+            String::new()
+        }
+
+        Self {
+            rules: Vec::new(),
+            ids: Vec::new(),
+            label: label::<E::Label>,
+            sites: None,
+            memoized: None,
+        }
+    }
+
+    /// Find the label of every labelled parser within `parser`, identified by its [`Site`].
+    pub(crate) fn sites<O, P: Parser<I, O, Error = E>>(parser: &P) -> Vec<(Site, Arc<E::Label>)>
+    where
+        I: Clone,
+    {
+        let mut describer = Self {
+            sites: Some(Vec::new()),
+            ..Self::finder()
+        };
+        parser.describe(&mut describer);
+        describer.sites.unwrap_or_default()
+    }
+
+    /// Find the [`Site`] of every memoized parser within `parser` (see [`Parser::memoized`]).
+    pub(crate) fn memoized_sites<O, P: Parser<I, O, Error = E>>(parser: &P) -> Vec<Site>
+    where
+        I: Clone,
+    {
+        let mut describer = Self {
+            memoized: Some(Vec::new()),
+            ..Self::finder()
+        };
+        parser.describe(&mut describer);
+        describer.memoized.unwrap_or_default()
+    }
+
+    /// Describe a label, as given to [`Parser::labelled`].
+    pub fn label(&self, label: E::Label) -> String {
+        (self.label)(label)
+    }
+
+    /// Describe a recursive parser identified by the address `ptr`, returning the index of its [`Rule`].
+    ///
+    /// If the parser has already been described, `define` is not called, allowing cycles to be represented.
+    pub(crate) fn rule(
+        &mut self,
+        ptr: *const (),
+        location: Location<'static>,
+        define: impl FnOnce(&mut Self) -> Node<I>,
+    ) -> usize {
+        if let Some((_, id)) = self.ids.iter().find(|(p, _)| *p == ptr) {
+            return *id;
+        }
+        let id = self.rules.len();
+        self.ids.push((ptr, id));
+        self.rules.push(Rule {
+            name: None,
+            node: None,
+            location,
+        });
+        let node = define(self);
+        let rule = &mut self.rules[id];
+        rule.name = node.label.clone();
+        rule.node = Some(node);
+        id
+    }
+
+    /// Record the label of the labelled parser `parser`, if looking for sites (see [`Describer::sites`]).
+    pub(crate) fn site<P>(&mut self, parser: &P, label: impl FnOnce() -> E::Label) {
+        if let Some(sites) = &mut self.sites {
+            sites.push((cst::site(parser), Arc::new(label())));
+        }
+    }
+
+    /// Record the memoized parser `parser`, if looking for memoized sites (see [`Describer::memoized_sites`]).
+    pub(crate) fn memoized<P>(&mut self, parser: &P) {
+        if let Some(memoized) = &mut self.memoized {
+            memoized.push(cst::site(parser));
+        }
+    }
+
+    /// Name the rule that `node` refers to after `label`, if it is a reference to a rule without a name.
+    pub(crate) fn name_rule(&mut self, node: &Node<I>, label: &str) {
+        let mut node = node;
+        while let Kind::Wrap(inner) = &node.kind {
+            node = inner;
+        }
+        if let Kind::Rule(id) = node.kind {
+            self.rules[id].name.get_or_insert_with(|| label.to_string());
+        }
+    }
+}
+
+/// A description of the structure of a parser and the [`Rule`]s within it.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, grammar::{Grammar, Kind}};
+/// let expr = recursive::<_, _, _, _, Simple<char>>(|expr| {
+///     expr.delimited_by(just('('), just(')'))
+///         .or(text::ident().map(|_| ()))
+/// })
+/// .labelled("expr");
+///
+/// let grammar = Grammar::of(&expr);
+/// assert_eq!(grammar.rules().len(), 1);
+/// assert_eq!(grammar.rules()[0].name(), Some("expr"));
+///
+/// // The root refers to the rule, and the rule refers to itself
+/// let rule = grammar.rules()[0].node().clone().simplify();
+/// match rule.kind() {
+///     Kind::Choice(alts) => assert_eq!(alts[0].children()[1].kind(), &Kind::Rule(0)),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grammar<I> {
+    root: Node<I>,
+    rules: Vec<Rule<I>>,
+}
+
+impl<I: Clone> Grammar<I> {
+    /// Describe the given parser.
+    pub fn of<O, P: Parser<I, O>>(parser: &P) -> Self
+    where
+        <P::Error as Error<I>>::Label: fmt::Display,
+    {
+        fn label<L: fmt::Display>(label: L) -> String {
+            label.to_string()
+        }
+
+        let mut describer = Describer {
+            rules: Vec::new(),
+            ids: Vec::new(),
+            label: label::<<P::Error as Error<I>>::Label>,
+            sites: None,
+            memoized: None,
+        };
+        let root = parser.describe(&mut describer);
+        Self {
+            root,
+            rules: describer.rules,
+        }
+    }
+}
+
+impl<I> Grammar<I> {
+    /// The node describing the parser.
+    pub fn root(&self) -> &Node<I> {
+        &self.root
+    }
+
+    /// The rules within the grammar, referred to by [`Kind::Rule`].
+    pub fn rules(&self) -> &[Rule<I>] {
+        &self.rules
+    }
+
+    /// Simplify the grammar, simplifying its root and the pattern of every rule (see [`Node::simplify`]).
+    pub fn simplify(self) -> Self {
+        Self {
+            root: self.root.simplify(),
+            rules: self
+                .rules
+                .into_iter()
+                .map(|rule| Rule {
+                    node: rule.node.map(Node::simplify),
+                    ..rule
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<I: Literal + Clone + PartialEq> Grammar<I> {
+    /// Render the grammar as [W3C-style EBNF](https://www.w3.org/TR/xml/#sec-notation).
+    ///
+    /// The first rule describes the parser itself, followed by a rule for each labelled parser (see
+    /// [`Parser::labelled`] and [`Parser::debug`]) and each [`Recursive`](recursive::Recursive) parser. Rules are named
+    /// after their labels, with any characters that cannot appear in a name replaced by `_`.
+    ///
+    /// Alongside the usual notation, repetitions with bounds are written as `x{n,m}`, lookahead (see
+    /// [`Parser::rewind`]) as `&x` and parsers with no known structure (such as [`filter`]) as `<name>`. Rules
+    /// describing the parsers in [`text`] that appear in the grammar are included at the end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, grammar::Grammar};
+    /// let value = recursive::<_, _, _, _, Simple<char>>(|value| {
+    ///     let list = value
+    ///         .padded()
+    ///         .separated_by(just(','))
+    ///         .delimited_by(just('['), just(']'))
+    ///         .ignored()
+    ///         .labelled("list");
+    ///
+    ///     text::int(10).ignored().or(list)
+    /// })
+    /// .labelled("value");
+    ///
+    /// assert_eq!(
+    ///     Grammar::of(&value.then_ignore(end())).to_ebnf(),
+    ///     "\
+    /// root ::= value <end>
+    /// value ::= int | list
+    /// list ::= \"[\" ( whitespace* value whitespace* ( \",\" whitespace* value whitespace* )* )? \"]\"
+    /// int ::= [1-9] [0-9]* | \"0\"
+    /// whitespace ::= [#x9-#xD#x20#x85#xA0#x1680#x2000-#x200A#x2028#x2029#x202F#x205F#x3000]
+    /// ",
+    /// );
+    /// ```
+    pub fn to_ebnf(&self) -> String {
+        let grammar = self.clone().simplify();
+        let named = Named::of(&grammar);
+
+        let mut ebnf = String::new();
+        for (name, node) in &named.rules {
+            ebnf += &format!("{} ::= {}\n", name, named.ebnf(node, true).0);
+        }
+        for name in &named.terminals {
+            if let Some((_, definition)) = TERMINALS.iter().find(|(n, _)| n == name) {
+                ebnf += &format!("{} ::= {}\n", name, definition);
+            }
+        }
+        ebnf
+    }
+
+    /// Draw a railroad diagram (also known as a syntax diagram) for each rule of the grammar, as standalone SVG
+    /// documents.
+    ///
+    /// The rules are the same as those written by [`Grammar::to_ebnf`]. Literal inputs are drawn in rounded boxes,
+    /// references to other rules and the parsers in [`text`] in square boxes, and parsers with no known structure
+    /// (such as [`filter`]) in dashed boxes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, grammar::Grammar};
+    /// let list = text::ident::<_, Simple<char>>()
+    ///     .labelled("item")
+    ///     .separated_by(just(','))
+    ///     .delimited_by(just('('), just(')'))
+    ///     .labelled("list");
+    ///
+    /// let diagrams = Grammar::of(&list).to_railroad();
+    /// assert_eq!(diagrams.len(), 2);
+    /// assert_eq!(diagrams[0].name(), "list");
+    /// assert_eq!(diagrams[1].name(), "item");
+    /// assert!(diagrams[0].svg().starts_with("<svg"));
+    /// assert!(diagrams[0].svg().contains(">item</text>"));
+    /// ```
+    pub fn to_railroad(&self) -> Vec<Diagram> {
+        let grammar = self.clone().simplify();
+        let named = Named::of(&grammar);
+        named
+            .rules
+            .iter()
+            .map(|(name, node)| Diagram {
+                name: name.clone(),
+                svg: named.track(node, true).to_svg(),
+            })
+            .collect()
+    }
+}
+
+impl<I: Clone + PartialEq> Grammar<I> {
+    /// Look for common mistakes in the grammar.
+    ///
+    /// This finds the following problems, which would otherwise only be discovered when parsing:
+    ///
+    /// - alternatives of [`Parser::or`] and [`choice`] that can never be chosen, because an earlier alternative
+    ///   always succeeds first (such as `just("a").or(just("ab"))`)
+    ///
+    /// - repetitions (see [`Parser::repeated`] and [`Parser::separated_by`]) of patterns that can succeed without
+    ///   consuming any input, which would repeat forever
+    ///
+    /// - [`Recursive`](recursive::Recursive) parsers that can refer to themselves without consuming any input (left
+    ///   recursion), which would recurse until the stack overflows
+    ///
+    /// To find these, the nullability (whether a pattern can succeed without consuming input) and first set (which
+    /// inputs a pattern can begin with) of each pattern are computed. Parsers with no known structure, such as
+    /// [`custom`], are assumed to consume input, so some problems may go unreported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, grammar::{Grammar, LintKind}};
+    /// let op = just::<_, _, Simple<char>>("-").or(just("->")).or(just("+"));
+    ///
+    /// let lints = Grammar::of(&op).lint();
+    /// assert_eq!(lints.len(), 1);
+    /// assert_eq!(lints[0].kind(), &LintKind::UnreachableAlternative { alternative: 1, shadowed_by: 0 });
+    /// assert_eq!(lints[0].location().unwrap().line(), line!() - 5);
+    /// ```
+    pub fn lint(&self) -> Vec<Lint> {
+        let analysis = Analysis::of(self);
+        let mut lints = Vec::new();
+        analysis.walk(&self.root, &mut lints);
+        for rule in &self.rules {
+            analysis.walk(rule.node(), &mut lints);
+        }
+        analysis.left_recursion(&mut lints);
+        lints
+    }
+}
+
+/// A railroad diagram for a single rule of a [`Grammar`]. See [`Grammar::to_railroad`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagram {
+    name: String,
+    svg: String,
+}
+
+impl Diagram {
+    /// The name of the rule that the diagram describes.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The diagram, as a standalone SVG document.
+    pub fn svg(&self) -> &str {
+        &self.svg
+    }
+}
+
+impl fmt::Display for Diagram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.svg)
+    }
+}
+
+/// A type of input that can be written as a literal when rendering a grammar, such as by [`Grammar::to_ebnf`].
+///
+/// This trait is implemented for [`char`] and [`u8`], which are written as quoted strings and character classes. To
+/// render grammars over your own tokens, implement it for your token type: by default, tokens are written using their
+/// [`fmt::Debug`] implementation.
+pub trait Literal: fmt::Debug + Sized {
+    /// Write the given sequence of inputs as a single literal.
+    fn fmt_literal(tokens: &[Self], f: &mut fmt::Formatter) -> fmt::Result {
+        match tokens {
+            [] => f.write_str("\"\""),
+            [token] => write!(f, "{:?}", token),
+            tokens => {
+                f.write_str("(")?;
+                for token in tokens {
+                    write!(f, " {:?}", token)?;
+                }
+                f.write_str(" )")
+            }
+        }
+    }
+
+    /// Write a pattern that matches any one of the given inputs or, if `negated` is true, any input except them.
+    fn fmt_class(tokens: &[Self], negated: bool, f: &mut fmt::Formatter) -> fmt::Result {
+        if negated {
+            f.write_str("( <any> - ")?;
+        }
+        match tokens {
+            [token] => write!(f, "{:?}", token)?,
+            tokens => {
+                f.write_str("(")?;
+                for (i, token) in tokens.iter().enumerate() {
+                    write!(f, "{} {:?}", if i == 0 { "" } else { " |" }, token)?;
+                }
+                f.write_str(" )")?;
+            }
+        }
+        if negated {
+            f.write_str(" )")?;
+        }
+        Ok(())
+    }
+}
+
+impl Literal for char {
+    fn fmt_literal(tokens: &[Self], f: &mut fmt::Formatter) -> fmt::Result {
+        write_string(tokens.iter().copied(), false, f)
+    }
+
+    fn fmt_class(tokens: &[Self], negated: bool, f: &mut fmt::Formatter) -> fmt::Result {
+        write_class(tokens.iter().copied(), negated, false, f)
+    }
+}
+
+impl Literal for u8 {
+    fn fmt_literal(tokens: &[Self], f: &mut fmt::Formatter) -> fmt::Result {
+        write_string(tokens.iter().map(|b| *b as char), true, f)
+    }
+
+    fn fmt_class(tokens: &[Self], negated: bool, f: &mut fmt::Formatter) -> fmt::Result {
+        write_class(tokens.iter().map(|b| *b as char), negated, true, f)
+    }
+}
+
+// Whether a character can be written as-is within a quoted string or character class, rather than as `#xN`.
+fn is_printable(c: char, ascii: bool) -> bool {
+    (c == ' ' || !(c.is_control() || c.is_whitespace())) && (c.is_ascii() || !ascii)
+}
+
+// Write a sequence of characters as a quoted string, writing unprintable characters as `#xN` between quoted parts.
+fn write_string(
+    chars: impl Iterator<Item = char> + Clone,
+    ascii: bool,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    let quote = if chars.clone().any(|c| c == '"') {
+        '\''
+    } else {
+        '"'
+    };
+    let mut parts = Vec::new();
+    let mut quoted = false;
+    for c in chars {
+        if is_printable(c, ascii) && c != quote {
+            if !quoted {
+                parts.push(String::from(quote));
+                quoted = true;
+            }
+            parts.last_mut().unwrap().push(c);
+        } else {
+            if quoted {
+                parts.last_mut().unwrap().push(quote);
+                quoted = false;
+            }
+            parts.push(format!("#x{:X}", c as u32));
+        }
+    }
+    if quoted {
+        parts.last_mut().unwrap().push(quote);
+    }
+    match parts.as_slice() {
+        [] => write!(f, "{}{}", quote, quote),
+        [part] => f.write_str(part),
+        parts => write!(f, "( {} )", parts.join(" ")),
+    }
+}
+
+// Write a character class such as `[abc]` or `[^abc]`.
+fn write_class(
+    chars: impl Iterator<Item = char>,
+    negated: bool,
+    ascii: bool,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    f.write_str(if negated { "[^" } else { "[" })?;
+    for c in chars {
+        if is_printable(c, ascii) && !matches!(c, ']' | '^' | '-' | '\\' | '#') {
+            write!(f, "{}", c)?;
+        } else {
+            write!(f, "#x{:X}", c as u32)?;
+        }
+    }
+    f.write_str("]")
+}
+
+// Renders a literal or class via the [`Literal`] trait.
+struct Lit<'a, I>(&'a [I], Option<bool>);
+
+impl<'a, I: Literal> fmt::Display for Lit<'a, I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.1 {
+            None => I::fmt_literal(self.0, f),
+            Some(negated) => I::fmt_class(self.0, negated, f),
+        }
+    }
+}
+
+// Definitions of the terminals created by the parsers in [`text`].
+const TERMINALS: &[(&str, &str)] = &[
+    ("ident", "[A-Za-z_] [A-Za-z0-9_]*"),
+    ("int", "[1-9] [0-9]* | \"0\""),
+    ("digits", "[0-9]+"),
+    ("newline", "#xD? #xA | [#xB#xC#xD#x85#x2028#x2029]"),
+    (
+        "whitespace",
+        "[#x9-#xD#x20#x85#xA0#x1680#x2000-#x200A#x2028#x2029#x202F#x205F#x3000]",
+    ),
+];
+
+// The precedence of a rendered pattern, used to decide where parentheses are needed.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Prec {
+    Choice,
+    Seq,
+    Atom,
+}
+
+// The named rules of a grammar: the parser itself, each labelled node and each rule created by a [`Recursive`]
+// parser, in the order in which they are first referred to.
+struct Named<'a, I> {
+    grammar: &'a Grammar<I>,
+    rules: Vec<(String, &'a Node<I>)>,
+    // The index of the rule for each labelled node, by address
+    labelled: Vec<(&'a Node<I>, usize)>,
+    // The index of the rule for each of the grammar's rules
+    recursive: Vec<Option<usize>>,
+    terminals: Vec<&'static str>,
+}
+
+impl<'a, I: PartialEq> Named<'a, I> {
+    fn of(grammar: &'a Grammar<I>) -> Self {
+        let mut named = Self {
+            grammar,
+            rules: Vec::new(),
+            labelled: Vec::new(),
+            recursive: vec![None; grammar.rules.len()],
+            terminals: Vec::new(),
+        };
+        match grammar.root.kind {
+            Kind::Rule(id) => {
+                named.rule(id);
+            }
+            _ => {
+                named.add(
+                    grammar.root.label.as_deref().unwrap_or("root"),
+                    &grammar.root,
+                );
+                named.collect(&grammar.root, true);
+            }
+        }
+        named
+    }
+
+    fn add(&mut self, name: &str, node: &'a Node<I>) -> usize {
+        let mut base: String = name
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '_' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        if base.is_empty() || base.starts_with(|c: char| c.is_ascii_digit() || c == '-') {
+            base.insert(0, '_');
+        }
+        let mut name = base.clone();
+        let mut n = 1;
+        while self.rules.iter().any(|(other, _)| *other == name)
+            || TERMINALS.iter().any(|(other, _)| *other == name)
+        {
+            n += 1;
+            name = format!("{}_{}", base, n);
+        }
+        self.rules.push((name, node));
+        self.rules.len() - 1
+    }
+
+    fn rule(&mut self, id: usize) -> usize {
+        if let Some(index) = self.recursive[id] {
+            return index;
+        }
+        let rule = &self.grammar.rules[id];
+        let index = self.add(rule.name().unwrap_or("rule"), rule.node());
+        self.recursive[id] = Some(index);
+        self.collect(rule.node(), true);
+        index
+    }
+
+    // Find the named rules within a node. The node at the top of a rule is never given a rule of its own.
+    fn collect(&mut self, node: &'a Node<I>, top: bool) {
+        match (&node.label, &node.kind) {
+            (_, Kind::Rule(id)) => {
+                self.rule(*id);
+            }
+            (Some(label), _) if !top => {
+                let existing = self
+                    .labelled
+                    .iter()
+                    .find(|(other, _)| *other == node)
+                    .map(|(_, index)| *index);
+                match existing {
+                    Some(index) => self.labelled.push((node, index)),
+                    None => {
+                        let index = self.add(label, node);
+                        self.labelled.push((node, index));
+                        self.collect(node, true);
+                    }
+                }
+            }
+            (_, Kind::Terminal(name)) => {
+                if !self.terminals.contains(name) {
+                    self.terminals.push(name);
+                }
+            }
+            _ => {
+                for child in node.children() {
+                    self.collect(child, false);
+                }
+            }
+        }
+    }
+
+    // The name of the rule that a node refers to, if any.
+    fn reference(&self, node: &Node<I>) -> Option<&str> {
+        let index = match node.kind {
+            Kind::Rule(id) => self.recursive[id]?,
+            _ if node.label.is_some() => {
+                self.labelled
+                    .iter()
+                    .find(|(other, _)| core::ptr::eq(*other, node))?
+                    .1
+            }
+            _ => return None,
+        };
+        Some(&self.rules[index].0)
+    }
+}
+
+impl<'a, I: Literal + PartialEq> Named<'a, I> {
+    fn ebnf_at(&self, node: &Node<I>, prec: Prec) -> String {
+        match self.ebnf(node, false) {
+            (ebnf, p) if p < prec => format!("( {} )", ebnf),
+            (ebnf, _) => ebnf,
+        }
+    }
+
+    fn ebnf(&self, node: &Node<I>, top: bool) -> (String, Prec) {
+        // A rule may consist of nothing but a reference to another rule
+        if let Some(name) = self
+            .reference(node)
+            .filter(|_| !top || matches!(node.kind, Kind::Rule(_)))
+        {
+            return (name.to_string(), Prec::Atom);
+        }
+        match &node.kind {
+            Kind::Just(tokens) => (Lit(tokens, None).to_string(), Prec::Atom),
+            Kind::OneOf(tokens) => (Lit(tokens, Some(false)).to_string(), Prec::Atom),
+            Kind::NoneOf(tokens) => (Lit(tokens, Some(true)).to_string(), Prec::Atom),
+            Kind::Filter | Kind::Opaque => (format!("<{}>", node.name), Prec::Atom),
+            Kind::End => ("<end>".to_string(), Prec::Atom),
+            Kind::Empty => ("\"\"".to_string(), Prec::Atom),
+            Kind::Seq(items) if items.is_empty() => ("\"\"".to_string(), Prec::Atom),
+            Kind::Terminal(name) => (name.to_string(), Prec::Atom),
+            Kind::Seq(items) if items.len() == 1 => self.ebnf(&items[0], false),
+            Kind::Seq(items) => (self.join(items, " ", Prec::Seq), Prec::Seq),
+            Kind::Choice(alts) if alts.len() == 1 => self.ebnf(&alts[0], false),
+            Kind::Choice(alts) => (self.join(alts, " | ", Prec::Seq), Prec::Choice),
+            Kind::Optional(item) => (format!("{}?", self.ebnf_at(item, Prec::Atom)), Prec::Atom),
+            Kind::Repeat {
+                item,
+                separator: None,
+                at_least,
+                at_most,
+                ..
+            } => (
+                format!(
+                    "{}{}",
+                    self.ebnf_at(item, Prec::Atom),
+                    repetition(*at_least, *at_most)
+                ),
+                Prec::Atom,
+            ),
+            Kind::Repeat {
+                item,
+                separator: Some(separator),
+                at_least,
+                at_most,
+                allow_leading,
+                allow_trailing,
+            } => {
+                if *at_most == Some(0) {
+                    return ("\"\"".to_string(), Prec::Atom);
+                }
+                let item = self.ebnf_at(item, Prec::Seq);
+                let separator = self.ebnf_at(separator, Prec::Atom);
+                let mut items = format!(
+                    "{} ( {} {} ){}",
+                    item,
+                    separator,
+                    item,
+                    repetition(at_least.saturating_sub(1), at_most.map(|m| m - 1))
+                );
+                if *allow_trailing {
+                    items = format!("{} {}?", items, separator);
+                }
+                if *at_least == 0 {
+                    items = format!("( {} )?", items);
+                }
+                if *allow_leading {
+                    items = format!("{}? {}", separator, items);
+                }
+                (items, Prec::Seq)
+            }
+            Kind::Wrap(inner) | Kind::TryMap(inner) => self.ebnf(inner, false),
+            Kind::Lookahead(inner) => (format!("&{}", self.ebnf_at(inner, Prec::Atom)), Prec::Atom),
+            Kind::Not(inner) => (
+                format!("( <any> - {} )", self.ebnf_at(inner, Prec::Atom)),
+                Prec::Atom,
+            ),
+            Kind::TakeUntil(until) => {
+                let until = self.ebnf_at(until, Prec::Atom);
+                (format!("( <any> - {} )* {}", until, until), Prec::Seq)
+            }
+            Kind::ThenWith(first) => (
+                format!("{} <{}>", self.ebnf_at(first, Prec::Seq), node.name),
+                Prec::Seq,
+            ),
+            // Every rule that is referred to is named, but fall back to treating the rule as opaque regardless
+            Kind::Rule(_) => (format!("<{}>", node.name), Prec::Atom),
+        }
+    }
+
+    fn join(&self, nodes: &[Node<I>], sep: &str, prec: Prec) -> String {
+        nodes
+            .iter()
+            .map(|node| self.ebnf_at(node, prec))
+            .collect::<Vec<_>>()
+            .join(sep)
+    }
+}
+
+// The suffix denoting a repetition with the given bounds.
+fn repetition(at_least: usize, at_most: Option<usize>) -> String {
+    match (at_least, at_most) {
+        (0, None) => "*".to_string(),
+        (1, None) => "+".to_string(),
+        (0, Some(1)) => "?".to_string(),
+        (n, None) => format!("{{{},}}", n),
+        (n, Some(m)) if n == m => format!("{{{}}}", n),
+        (n, Some(m)) => format!("{{{},{}}}", n, m),
+    }
+}
+
+impl<'a, I: Literal + PartialEq> Named<'a, I> {
+    fn track(&self, node: &Node<I>, top: bool) -> Track {
+        // A rule may consist of nothing but a reference to another rule
+        if let Some(name) = self
+            .reference(node)
+            .filter(|_| !top || matches!(node.kind, Kind::Rule(_)))
+        {
+            return Track::Rule(name.to_string());
+        }
+        match &node.kind {
+            Kind::Just(tokens) => Track::Literal(Lit(tokens, None).to_string()),
+            Kind::OneOf(tokens) => Track::Literal(Lit(tokens, Some(false)).to_string()),
+            Kind::NoneOf(tokens) => Track::Literal(Lit(tokens, Some(true)).to_string()),
+            Kind::Filter | Kind::Opaque => Track::Special(node.name.to_string()),
+            Kind::End => Track::Special("end".to_string()),
+            Kind::Empty => Track::Skip,
+            Kind::Terminal(name) => Track::Rule(name.to_string()),
+            Kind::Seq(items) => {
+                Track::seq(items.iter().map(|item| self.track(item, false)).collect())
+            }
+            Kind::Choice(alts) if alts.len() == 1 => self.track(&alts[0], false),
+            Kind::Choice(alts) => {
+                Track::choice(alts.iter().map(|alt| self.track(alt, false)).collect())
+            }
+            Kind::Optional(item) => Track::optional(self.track(item, false)),
+            Kind::Repeat {
+                item,
+                separator,
+                at_least,
+                at_most,
+                allow_leading,
+                allow_trailing,
+            } => {
+                let item = self.track(item, false);
+                let separator = separator.as_ref().map(|sep| self.track(sep, false));
+                let items = match (at_least, at_most) {
+                    (_, Some(0)) => Track::Skip,
+                    (0, Some(1)) => Track::optional(item),
+                    _ => {
+                        let note = match (at_least, at_most) {
+                            (0, None) | (1, None) => None,
+                            (n, None) => Some(format!("at least {} times", n)),
+                            (n, Some(m)) if n == m => Some(format!("{} times", n)),
+                            (n, Some(m)) => Some(format!("{} to {} times", n, m)),
+                        };
+                        let repeat =
+                            Track::Loop(Box::new(item), separator.clone().map(Box::new), note);
+                        if *at_least == 0 {
+                            Track::optional(repeat)
+                        } else {
+                            repeat
+                        }
+                    }
+                };
+                match separator {
+                    Some(sep) if *allow_leading || *allow_trailing => {
+                        let mut tracks = Vec::new();
+                        if *allow_leading {
+                            tracks.push(Track::optional(sep.clone()));
+                        }
+                        tracks.push(items);
+                        if *allow_trailing {
+                            tracks.push(Track::optional(sep));
+                        }
+                        Track::seq(tracks)
+                    }
+                    _ => items,
+                }
+            }
+            Kind::Wrap(inner) | Kind::TryMap(inner) => self.track(inner, false),
+            Kind::Lookahead(_) | Kind::Not(_) => Track::Special(self.ebnf(node, true).0),
+            Kind::TakeUntil(until) => Track::seq(vec![
+                Track::Special(format!("( <any> - {} )*", self.ebnf_at(until, Prec::Atom))),
+                self.track(until, false),
+            ]),
+            Kind::ThenWith(first) => Track::seq(vec![
+                self.track(first, false),
+                Track::Special(node.name.to_string()),
+            ]),
+            // Every rule that is referred to is named, but fall back to treating the rule as opaque regardless
+            Kind::Rule(_) => Track::Special(node.name.to_string()),
+        }
+    }
+}
+
+// The dimensions used to lay out railroad diagrams, in pixels.
+const CHAR_WIDTH: i32 = 8;
+const BOX_HEIGHT: i32 = 24;
+const BOX_PADDING: i32 = 10;
+const GAP: i32 = 10;
+const ARC: i32 = 10;
+const MARGIN: i32 = 20;
+const NOTE_HEIGHT: i32 = 16;
+
+const STYLE: &str = "\
+path { stroke: #333; stroke-width: 2; fill: none; }
+rect { stroke: #333; stroke-width: 2; fill: #fff8dc; }
+rect.rule { fill: #e6f0ff; }
+rect.special { fill: #eee; stroke-dasharray: 4 2; }
+text { font: 13px monospace; text-anchor: middle; dominant-baseline: central; }
+text.note { font-size: 11px; fill: #555; }";
+
+// A piece of a railroad diagram. Each track is drawn between two points on a horizontal line, its baseline, and
+// extends above and below it.
+#[derive(Clone)]
+enum Track {
+    Literal(String),
+    Rule(String),
+    Special(String),
+    Skip,
+    Seq(Vec<Track>),
+    Choice(Vec<Track>),
+    // An item that may be repeated, with a track to follow between repetitions and a note about their number
+    Loop(Box<Track>, Option<Box<Track>>, Option<String>),
+}
+
+impl Track {
+    fn seq(mut tracks: Vec<Track>) -> Self {
+        tracks.retain(|track| !matches!(track, Track::Skip));
+        match tracks.len() {
+            0 => Track::Skip,
+            1 => tracks.remove(0),
+            _ => Track::Seq(tracks),
+        }
+    }
+
+    // A choice with no alternatives can never be taken, so it is drawn as nothing at all.
+    fn choice(alts: Vec<Track>) -> Self {
+        if alts.is_empty() {
+            Track::Skip
+        } else {
+            Track::Choice(alts)
+        }
+    }
+
+    fn optional(track: Track) -> Self {
+        Track::Choice(vec![Track::Skip, track])
+    }
+
+    // The width of the track, and its extent above and below its baseline.
+    fn size(&self) -> (i32, i32, i32) {
+        match self {
+            Track::Literal(text) | Track::Rule(text) | Track::Special(text) => (
+                text.chars().count() as i32 * CHAR_WIDTH + 2 * BOX_PADDING,
+                BOX_HEIGHT / 2,
+                BOX_HEIGHT / 2,
+            ),
+            Track::Skip => (0, 0, 0),
+            Track::Seq(tracks) => tracks.iter().map(Track::size).fold(
+                (GAP * (tracks.len() as i32 - 1), 0, 0),
+                |(w, up, down), (w2, up2, down2)| (w + w2, up.max(up2), down.max(down2)),
+            ),
+            Track::Choice(alts) => {
+                let offsets = Self::offsets(alts);
+                let width = alts.iter().map(|alt| alt.size().0).max().unwrap_or(0);
+                (
+                    width + 4 * ARC,
+                    alts[0].size().1,
+                    offsets.last().unwrap() + alts.last().unwrap().size().2,
+                )
+            }
+            Track::Loop(item, back, note) => {
+                let (w, up, _) = item.size();
+                let back_size = back.as_ref().map_or((0, 0, 0), |back| back.size());
+                (
+                    w.max(back_size.0) + 2 * ARC,
+                    up,
+                    self.loop_offset() + back_size.2 + note.as_ref().map_or(0, |_| NOTE_HEIGHT),
+                )
+            }
+        }
+    }
+
+    // The offsets of the baselines of each alternative below the baseline of the first.
+    fn offsets(alts: &[Track]) -> Vec<i32> {
+        let mut offsets = vec![0];
+        for pair in alts.windows(2) {
+            let offset = offsets.last().unwrap() + pair[0].size().2 + GAP + pair[1].size().1;
+            offsets.push(if offsets.len() == 1 {
+                offset.max(2 * ARC)
+            } else {
+                offset
+            });
+        }
+        offsets
+    }
+
+    // The offset of the baseline of the return path of a loop below the baseline of its item.
+    fn loop_offset(&self) -> i32 {
+        match self {
+            Track::Loop(item, back, _) => {
+                (item.size().2 + GAP + back.as_ref().map_or(0, |back| back.size().1)).max(2 * ARC)
+            }
+            _ => 0,
+        }
+    }
+
+    // Draw the track with the left end of its baseline at `(x, y)`.
+    fn draw(&self, x: i32, y: i32, svg: &mut String) {
+        let (width, _, _) = self.size();
+        match self {
+            Track::Literal(text) | Track::Rule(text) | Track::Special(text) => {
+                let (class, rx) = match self {
+                    Track::Literal(_) => ("literal", BOX_HEIGHT / 2),
+                    Track::Rule(_) => ("rule", 0),
+                    _ => ("special", 0),
+                };
+                *svg += &format!(
+                    "<rect class=\"{}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\"/>\n",
+                    class,
+                    x,
+                    y - BOX_HEIGHT / 2,
+                    width,
+                    BOX_HEIGHT,
+                    rx,
+                );
+                *svg += &format!(
+                    "<text x=\"{}\" y=\"{}\">{}</text>\n",
+                    x + width / 2,
+                    y,
+                    escape_xml(text),
+                );
+            }
+            Track::Skip => {}
+            Track::Seq(tracks) => {
+                let mut x = x;
+                for (i, track) in tracks.iter().enumerate() {
+                    if i > 0 {
+                        line(svg, x, y, GAP);
+                        x += GAP;
+                    }
+                    track.draw(x, y, svg);
+                    x += track.size().0;
+                }
+            }
+            Track::Choice(alts) => {
+                for (alt, offset) in alts.iter().zip(Self::offsets(alts)) {
+                    let alt_width = alt.size().0;
+                    if offset == 0 {
+                        line(svg, x, y, 2 * ARC);
+                    } else {
+                        *svg += &format!(
+                            "<path d=\"M{} {} a{r} {r} 0 0 1 {r} {r} v{} a{r} {r} 0 0 0 {r} {r}\"/>\n",
+                            x,
+                            y,
+                            offset - 2 * ARC,
+                            r = ARC,
+                        );
+                    }
+                    alt.draw(x + 2 * ARC, y + offset, svg);
+                    line(
+                        svg,
+                        x + 2 * ARC + alt_width,
+                        y + offset,
+                        width - 4 * ARC - alt_width,
+                    );
+                    if offset == 0 {
+                        line(svg, x + width - 2 * ARC, y, 2 * ARC);
+                    } else {
+                        *svg += &format!(
+                            "<path d=\"M{} {} a{r} {r} 0 0 0 {r} -{r} v-{} a{r} {r} 0 0 1 {r} -{r}\"/>\n",
+                            x + width - 2 * ARC,
+                            y + offset,
+                            offset - 2 * ARC,
+                            r = ARC,
+                        );
+                    }
+                }
+            }
+            Track::Loop(item, back, note) => {
+                let inner = width - 2 * ARC;
+                let (item_width, _, _) = item.size();
+                line(svg, x, y, ARC);
+                item.draw(x + ARC, y, svg);
+                line(svg, x + ARC + item_width, y, width - ARC - item_width);
+
+                let back_y = y + self.loop_offset();
+                *svg += &format!(
+                    "<path d=\"M{} {} a{r} {r} 0 0 1 {r} {r} v{} a{r} {r} 0 0 1 -{r} {r}\"/>\n",
+                    x + width - ARC,
+                    y,
+                    back_y - y - 2 * ARC,
+                    r = ARC,
+                );
+                *svg += &format!(
+                    "<path d=\"M{} {} a{r} {r} 0 0 1 -{r} -{r} v-{} a{r} {r} 0 0 1 {r} -{r}\"/>\n",
+                    x + ARC,
+                    back_y,
+                    back_y - y - 2 * ARC,
+                    r = ARC,
+                );
+                let (back_width, _, back_down) =
+                    back.as_ref().map_or((0, 0, 0), |back| back.size());
+                let back_x = x + ARC + (inner - back_width) / 2;
+                line(svg, x + ARC, back_y, back_x - x - ARC);
+                if let Some(back) = back {
+                    back.draw(back_x, back_y, svg);
+                }
+                line(
+                    svg,
+                    back_x + back_width,
+                    back_y,
+                    x + width - ARC - back_x - back_width,
+                );
+                if let Some(note) = note {
+                    *svg += &format!(
+                        "<text class=\"note\" x=\"{}\" y=\"{}\">{}</text>\n",
+                        x + width / 2,
+                        back_y + back_down + NOTE_HEIGHT / 2 + 2,
+                        escape_xml(note),
+                    );
+                }
+            }
+        }
+    }
+
+    fn to_svg(&self) -> String {
+        let (width, up, down) = self.size();
+        let total_width = width + 2 * MARGIN + 2 * GAP;
+        let y = MARGIN + up;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+             <style>\n{}\n</style>\n",
+            STYLE,
+            w = total_width,
+            h = up + down + 2 * MARGIN,
+        );
+        // The start and end of the diagram are marked with short vertical bars
+        svg += &format!(
+            "<path d=\"M{} {} v{}\"/>\n<path d=\"M{} {} v{}\"/>\n",
+            MARGIN,
+            y - ARC,
+            2 * ARC,
+            total_width - MARGIN,
+            y - ARC,
+            2 * ARC,
+        );
+        line(&mut svg, MARGIN, y, GAP);
+        self.draw(MARGIN + GAP, y, &mut svg);
+        line(&mut svg, MARGIN + GAP + width, y, GAP);
+        svg += "</svg>\n";
+        svg
+    }
+}
+
+// Draw a horizontal line of the given length.
+fn line(svg: &mut String, x: i32, y: i32, length: i32) {
+    if length > 0 {
+        *svg += &format!("<path d=\"M{} {} h{}\"/>\n", x, y, length);
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped += "&amp;",
+            '<' => escaped += "&lt;",
+            '>' => escaped += "&gt;",
+            '"' => escaped += "&quot;",
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A kind of problem found by [`Grammar::lint`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LintKind {
+    /// An alternative of a choice that can never be chosen, because an earlier alternative always succeeds first on
+    /// any input that it would match.
+    UnreachableAlternative {
+        /// The index of the unreachable alternative within the choice.
+        alternative: usize,
+        /// The index of the earlier alternative that succeeds first.
+        shadowed_by: usize,
+    },
+    /// A repetition of a pattern that can succeed without consuming any input, which would repeat forever.
+    EmptyLoop,
+    /// A [`Recursive`](recursive::Recursive) parser that can refer to itself without consuming any input, which would
+    /// recurse until the stack overflows.
+    LeftRecursion {
+        /// The names of the rules through which the parser refers to itself, starting and ending with the parser.
+        cycle: Vec<String>,
+    },
+}
+
+/// A problem found by [`Grammar::lint`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lint {
+    kind: LintKind,
+    location: Option<Location<'static>>,
+}
+
+impl Lint {
+    /// The kind of problem.
+    pub fn kind(&self) -> &LintKind {
+        &self.kind
+    }
+
+    /// The location in the source code of the combinator with the problem: the call to [`Parser::or`] or [`choice`]
+    /// that introduced an unreachable alternative, the call to [`Parser::repeated`] or [`Parser::separated_by`] for
+    /// an empty loop, or the declaration of a left-recursive [`Recursive`](recursive::Recursive) parser.
+    pub fn location(&self) -> Option<Location<'static>> {
+        self.location
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(location) = self.location {
+            write!(f, "{}: ", location)?;
+        }
+        match &self.kind {
+            LintKind::UnreachableAlternative {
+                alternative,
+                shadowed_by,
+            } => write!(
+                f,
+                "alternative {} can never be chosen, because alternative {} always succeeds first",
+                alternative + 1,
+                shadowed_by + 1,
+            ),
+            LintKind::EmptyLoop => write!(
+                f,
+                "the repeated pattern can succeed without consuming input, so it would repeat forever",
+            ),
+            LintKind::LeftRecursion { cycle } => write!(
+                f,
+                "the parser can refer to itself without consuming input ({}), so it would recurse forever",
+                cycle.join(" -> "),
+            ),
+        }
+    }
+}
+
+// The inputs that a pattern can begin with.
+#[derive(Clone, PartialEq)]
+struct First<I> {
+    tokens: Vec<I>,
+    // Whether the pattern can begin with inputs that are not known
+    any: bool,
+}
+
+impl<I: Clone + PartialEq> First<I> {
+    fn none() -> Self {
+        Self {
+            tokens: Vec::new(),
+            any: false,
+        }
+    }
+
+    fn any() -> Self {
+        Self {
+            tokens: Vec::new(),
+            any: true,
+        }
+    }
+
+    fn union(mut self, other: Self) -> Self {
+        for token in other.tokens {
+            if !self.tokens.contains(&token) {
+                self.tokens.push(token);
+            }
+        }
+        self.any |= other.any;
+        self
+    }
+}
+
+// The nullability and first set of each rule of a grammar, used to find problems within it.
+struct Analysis<'a, I> {
+    grammar: &'a Grammar<I>,
+    nullable: Vec<bool>,
+    first: Vec<First<I>>,
+}
+
+impl<'a, I: Clone + PartialEq> Analysis<'a, I> {
+    fn of(grammar: &'a Grammar<I>) -> Self {
+        let mut analysis = Self {
+            grammar,
+            nullable: vec![false; grammar.rules.len()],
+            first: vec![First::none(); grammar.rules.len()],
+        };
+        // Rules may refer to one another, so iterate until a fixed point is reached
+        loop {
+            let mut changed = false;
+            for (id, rule) in grammar.rules.iter().enumerate() {
+                let nullable = analysis.nullable(rule.node());
+                let first = analysis.first(rule.node());
+                if nullable != analysis.nullable[id] || first != analysis.first[id] {
+                    analysis.nullable[id] = nullable;
+                    analysis.first[id] = first;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break analysis;
+            }
+        }
+    }
+
+    // Whether the node can succeed without consuming any input.
+    fn nullable(&self, node: &Node<I>) -> bool {
+        match &node.kind {
+            Kind::Just(tokens) => tokens.is_empty(),
+            Kind::OneOf(_)
+            | Kind::NoneOf(_)
+            | Kind::Filter
+            | Kind::Terminal(_)
+            | Kind::Not(_)
+            | Kind::Opaque => false,
+            Kind::End | Kind::Empty | Kind::Optional(_) | Kind::Lookahead(_) => true,
+            Kind::Seq(items) => items.iter().all(|item| self.nullable(item)),
+            Kind::Choice(alts) => alts.iter().any(|alt| self.nullable(alt)),
+            Kind::Repeat {
+                item,
+                separator,
+                at_least,
+                ..
+            } => {
+                *at_least == 0
+                    || (self.nullable(item)
+                        && (*at_least == 1 || separator.iter().all(|s| self.nullable(s))))
+            }
+            Kind::Wrap(inner) | Kind::TryMap(inner) | Kind::TakeUntil(inner) => {
+                self.nullable(inner)
+            }
+            // The second parser is unknown, so assume that it consumes input
+            Kind::ThenWith(_) => false,
+            Kind::Rule(id) => self.nullable[*id],
+        }
+    }
+
+    // The inputs that the node can begin with.
+    fn first(&self, node: &Node<I>) -> First<I> {
+        match &node.kind {
+            Kind::Just(tokens) => First {
+                tokens: tokens.iter().take(1).cloned().collect(),
+                any: false,
+            },
+            Kind::OneOf(tokens) => First {
+                tokens: tokens.clone(),
+                any: false,
+            },
+            Kind::NoneOf(_)
+            | Kind::Filter
+            | Kind::Terminal(_)
+            | Kind::Opaque
+            | Kind::Not(_)
+            | Kind::TakeUntil(_) => First::any(),
+            Kind::End | Kind::Empty => First::none(),
+            Kind::Seq(items) => {
+                let mut first = First::none();
+                for item in items {
+                    first = first.union(self.first(item));
+                    if !self.nullable(item) {
+                        break;
+                    }
+                }
+                first
+            }
+            Kind::Choice(alts) => alts
+                .iter()
+                .fold(First::none(), |first, alt| first.union(self.first(alt))),
+            Kind::Repeat {
+                item,
+                separator,
+                allow_leading,
+                ..
+            } => match separator {
+                Some(separator) if *allow_leading || self.nullable(item) => {
+                    self.first(item).union(self.first(separator))
+                }
+                _ => self.first(item),
+            },
+            Kind::Optional(inner)
+            | Kind::Wrap(inner)
+            | Kind::TryMap(inner)
+            | Kind::Lookahead(inner) => self.first(inner),
+            Kind::ThenWith(first) if self.nullable(first) => self.first(first).union(First::any()),
+            Kind::ThenWith(first) => self.first(first),
+            Kind::Rule(id) => self.first[*id].clone(),
+        }
+    }
+
+    // Whether the node always succeeds, whatever the input.
+    fn infallible(&self, node: &Node<I>) -> bool {
+        match &node.kind {
+            Kind::Just(tokens) => tokens.is_empty(),
+            Kind::Empty | Kind::Optional(_) => true,
+            Kind::Repeat { at_least, .. } => *at_least == 0,
+            Kind::Seq(items) => items.iter().all(|item| self.infallible(item)),
+            Kind::Choice(alts) => alts.iter().any(|alt| self.infallible(alt)),
+            Kind::Wrap(inner) | Kind::Lookahead(inner) => self.infallible(inner),
+            _ => false,
+        }
+    }
+
+    // The exact sequence of inputs that the node matches, if it only matches one.
+    fn exact(&self, node: &Node<I>) -> Option<Vec<I>> {
+        match &node.kind {
+            Kind::Just(tokens) => Some(tokens.clone()),
+            Kind::Empty => Some(Vec::new()),
+            Kind::Seq(items) => items.iter().try_fold(Vec::new(), |mut tokens, item| {
+                tokens.extend(self.exact(item)?);
+                Some(tokens)
+            }),
+            // The inputs matched within a `Kind::TryMap` might still be rejected (keywords only match whole
+            // identifiers, for example), so it has no exact match
+            Kind::Wrap(inner) => self.exact(inner),
+            _ => None,
+        }
+    }
+
+    // The sequence of inputs that every input matched by the node begins with.
+    fn prefix(&self, node: &Node<I>, depth: usize) -> Vec<I> {
+        match &node.kind {
+            Kind::Just(tokens) => tokens.clone(),
+            Kind::OneOf(tokens) if tokens.len() == 1 => tokens.clone(),
+            Kind::Seq(items) => {
+                let mut prefix = Vec::new();
+                for item in items {
+                    match self.exact(item) {
+                        Some(tokens) => prefix.extend(tokens),
+                        None => {
+                            prefix.extend(self.prefix(item, depth));
+                            break;
+                        }
+                    }
+                }
+                prefix
+            }
+            Kind::Choice(alts) => {
+                let mut prefixes = alts.iter().map(|alt| self.prefix(alt, depth));
+                let mut prefix = prefixes.next().unwrap_or_default();
+                for other in prefixes {
+                    let common = prefix
+                        .iter()
+                        .zip(&other)
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    prefix.truncate(common);
+                }
+                prefix
+            }
+            Kind::Repeat {
+                item,
+                at_least,
+                allow_leading: false,
+                ..
+            } if *at_least > 0 => self.prefix(item, depth),
+            Kind::Wrap(inner) | Kind::TryMap(inner) => self.prefix(inner, depth),
+            // Recursive rules may have infinitely long prefixes, so limit how deep we look
+            Kind::Rule(id) if depth < 8 => self.prefix(self.grammar.rules[*id].node(), depth + 1),
+            _ => Vec::new(),
+        }
+    }
+
+    // Whether the alternative `earlier` always succeeds first when the alternative `later` would match.
+    fn shadows(&self, earlier: &Node<I>, later: &Node<I>) -> bool {
+        if self.infallible(earlier) {
+            return true;
+        }
+        if let Some(tokens) = self.exact(earlier) {
+            if !tokens.is_empty() && self.prefix(later, 0).starts_with(&tokens) {
+                return true;
+            }
+        }
+        let mut class = earlier;
+        while let Kind::Wrap(inner) = &class.kind {
+            class = inner;
+        }
+        if let Kind::OneOf(tokens) = &class.kind {
+            let first = self.first(later);
+            return !first.any
+                && !self.nullable(later)
+                && first.tokens.iter().all(|token| tokens.contains(token));
+        }
+        false
+    }
+
+    // Find the alternatives of a choice, including those of unlabelled choices within it, along with the location
+    // of the choice that introduced each of them.
+    fn alternatives<'b>(
+        node: &'b Node<I>,
+        location: Option<Location<'static>>,
+        alts: &mut Vec<(&'b Node<I>, Option<Location<'static>>)>,
+    ) {
+        for alt in node.children() {
+            match &alt.kind {
+                Kind::Choice(_) if alt.label.is_none() => {
+                    Self::alternatives(alt, alt.location.or(location), alts)
+                }
+                _ => alts.push((alt, location)),
+            }
+        }
+    }
+
+    fn walk(&self, node: &Node<I>, lints: &mut Vec<Lint>) {
+        let mut push = |lint: Lint| {
+            if !lints.contains(&lint) {
+                lints.push(lint);
+            }
+        };
+        match &node.kind {
+            Kind::Choice(_) => {
+                let mut alts = Vec::new();
+                Self::alternatives(node, node.location, &mut alts);
+                for (alternative, (later, location)) in alts.iter().enumerate() {
+                    if let Some(shadowed_by) = alts[..alternative]
+                        .iter()
+                        .position(|(earlier, _)| self.shadows(earlier, later))
+                    {
+                        push(Lint {
+                            kind: LintKind::UnreachableAlternative {
+                                alternative,
+                                shadowed_by,
+                            },
+                            location: *location,
+                        });
+                    }
+                }
+                for (alt, _) in alts {
+                    self.walk(alt, lints);
+                }
+                return;
+            }
+            Kind::Repeat {
+                item, separator, ..
+            } if self.nullable(item) && separator.iter().all(|s| self.nullable(s)) => push(Lint {
+                kind: LintKind::EmptyLoop,
+                location: node.location,
+            }),
+            _ => {}
+        }
+        for child in node.children() {
+            self.walk(child, lints);
+        }
+    }
+
+    // Find the rules that the node can refer to before consuming any input.
+    fn leftmost(&self, node: &Node<I>, rules: &mut Vec<usize>) {
+        match &node.kind {
+            Kind::Rule(id) => {
+                if !rules.contains(id) {
+                    rules.push(*id);
+                }
+            }
+            Kind::Seq(items) => {
+                for item in items {
+                    self.leftmost(item, rules);
+                    if !self.nullable(item) {
+                        break;
+                    }
+                }
+            }
+            Kind::Repeat {
+                item,
+                separator,
+                allow_leading,
+                ..
+            } => {
+                self.leftmost(item, rules);
+                if let Some(separator) = separator {
+                    if *allow_leading || self.nullable(item) {
+                        self.leftmost(separator, rules);
+                    }
+                }
+            }
+            _ => {
+                for child in node.children() {
+                    self.leftmost(child, rules);
+                }
+            }
+        }
+    }
+
+    fn left_recursion(&self, lints: &mut Vec<Lint>) {
+        let rules = &self.grammar.rules;
+        let edges = rules
+            .iter()
+            .map(|rule| {
+                let mut leftmost = Vec::new();
+                self.leftmost(rule.node(), &mut leftmost);
+                leftmost
+            })
+            .collect::<Vec<_>>();
+
+        // Find a path from `from` back to `to`, through rules not yet visited
+        fn path(
+            edges: &[Vec<usize>],
+            from: usize,
+            to: usize,
+            visited: &mut Vec<bool>,
+        ) -> Option<Vec<usize>> {
+            for &next in &edges[from] {
+                if next == to {
+                    return Some(vec![from, to]);
+                } else if !visited[next] {
+                    visited[next] = true;
+                    if let Some(mut path) = path(edges, next, to, visited) {
+                        path.insert(0, from);
+                        return Some(path);
+                    }
+                }
+            }
+            None
+        }
+
+        for (id, rule) in rules.iter().enumerate() {
+            let cycle = match path(&edges, id, id, &mut vec![false; rules.len()]) {
+                // Report each cycle only once, at the first of its rules
+                Some(cycle) if cycle.iter().all(|other| *other >= id) => cycle,
+                _ => continue,
+            };
+            lints.push(Lint {
+                kind: LintKind::LeftRecursion {
+                    cycle: cycle
+                        .into_iter()
+                        .map(|id| match rules[id].name() {
+                            Some(name) => name.to_string(),
+                            None => format!("#{}", id),
+                        })
+                        .collect(),
+                },
+                location: Some(rule.location()),
+            });
+        }
+    }
+}
+
+// A parser that describes itself with the given function rather than by its structure, used to give the parsers in
+// [`text`] readable descriptions.
+#[derive(Copy, Clone)]
+pub(crate) struct Described<A, F>(pub(crate) A, pub(crate) F);
+
+impl<I: Clone, O, A: Parser<I, O, Error = E>, F: Fn() -> Node<I>, E: Error<I>> Parser<I, O>
+    for Described<A, F>
+{
+    type Error = E;
+
+    #[inline]
+    fn parse_inner<D: Debugger>(
+        &self,
+        debugger: &mut D,
+        stream: &mut StreamOf<I, E>,
+    ) -> PResult<I, O, E> {
+        #[allow(deprecated)]
+        self.0.parse_inner(debugger, stream)
+    }
+
+    fn describe(&self, _: &mut Describer<I, E>) -> Node<I> {
+        (self.1)()
+    }
+
+    #[inline]
+    fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+    #[inline]
+    fn parse_inner_silent(&self, d: &mut Silent, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Simple, text::TextParser};
+
+    #[test]
+    fn ebnf_rule_of_rule() {
+        let inner = recursive::recursive::<_, _, _, _, Simple<char>>(|inner| {
+            just('a').then(inner.or_not()).to(())
+        })
+        .labelled("inner");
+        let outer = recursive::recursive(|_| inner.clone());
+
+        // The outer rule takes its name from the parser that defines it, and consists of a reference to the inner rule
+        let grammar = Grammar::of(&outer);
+        assert_eq!(
+            grammar.to_ebnf(),
+            "inner ::= inner_2\ninner_2 ::= \"a\" inner_2?\n",
+        );
+    }
+
+    #[test]
+    fn railroad_rule_of_rule() {
+        let inner = recursive::recursive::<_, _, _, _, Simple<char>>(|inner| {
+            just('a').then(inner.or_not()).to(())
+        })
+        .labelled("inner");
+        let outer = recursive::recursive(|_| inner.clone());
+
+        let diagrams = Grammar::of(&outer).to_railroad();
+        assert_eq!(diagrams.len(), 2);
+        assert!(diagrams[0].svg().contains(">inner_2</text>"));
+    }
+
+    #[test]
+    fn railroad_empty_choice() {
+        let alts: Vec<Just<char, char, Simple<char>>> = Vec::new();
+        let parser = choice(alts).labelled("nothing");
+
+        let diagrams = Grammar::of(&parser).to_railroad();
+        assert_eq!(diagrams.len(), 1);
+        assert!(diagrams[0].svg().starts_with("<svg"));
+    }
+
+    #[test]
+    fn describe_grammar() {
+        let list = recursive::recursive::<_, _, _, _, Simple<char>>(|list| {
+            list.separated_by(just(','))
+                .allow_trailing()
+                .delimited_by(just('['), just(']'))
+                .ignored()
+                .or(text::keyword("nil"))
+                .labelled("list")
+        });
+        let parser = list.then_ignore(end());
+
+        let grammar = Grammar::of(&parser);
+        assert_eq!(grammar.rules().len(), 1);
+        let rule = &grammar.rules()[0];
+        assert_eq!(rule.name(), Some("list"));
+
+        let node = rule.node().clone().simplify();
+        assert_eq!(node.label(), Some("list"));
+        let alts = match node.kind() {
+            Kind::Choice(alts) => alts,
+            kind => panic!("unexpected kind: {:?}", kind),
+        };
+        assert_eq!(alts[0].name(), "delimited_by");
+        assert_eq!(alts[0].children()[0].tokens(), &['[']);
+        match alts[0].children()[1].kind() {
+            Kind::Repeat {
+                item,
+                separator: Some(separator),
+                allow_trailing: true,
+                ..
+            } => {
+                assert_eq!(item.kind(), &Kind::Rule(0));
+                assert_eq!(separator.tokens(), &[',']);
+            }
+            kind => panic!("unexpected kind: {:?}", kind),
+        }
+        assert_eq!(alts[1].name(), "keyword");
+        match alts[1].kind() {
+            Kind::TryMap(ident) => assert_eq!(ident.kind(), &Kind::Just(vec!['n', 'i', 'l'])),
+            kind => panic!("unexpected kind: {:?}", kind),
+        }
+
+        let root = grammar.root().clone().simplify();
+        match root.kind() {
+            Kind::Seq(items) => {
+                assert_eq!(items[0].kind(), &Kind::Rule(0));
+                assert_eq!(items[1].kind(), &Kind::End);
+            }
+            kind => panic!("unexpected kind: {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn grammar_to_ebnf() {
+        let parser = one_of::<_, _, Simple<char>>("ab")
+            .repeated()
+            .at_least(2)
+            .at_most(4)
+            .then(none_of("]\n").or_not())
+            .then(just("say \"hi\"\n").labelled("greeting"))
+            .then(
+                filter(|c: &char| c.is_numeric())
+                    .separated_by(just('.'))
+                    .at_least(1),
+            )
+            .labelled("line");
+
+        assert_eq!(
+            Grammar::of(&parser).to_ebnf(),
+            "line ::= [ab]{2,4} [^#x5D#xA]? greeting <filter> ( \".\" <filter> )*\n\
+             greeting ::= ( 'say \"hi\"' #xA )\n",
+        );
+    }
+
+    #[test]
+    fn grammar_to_railroad() {
+        let expr = recursive::recursive::<_, _, _, _, Simple<char>>(|expr| {
+            expr.delimited_by(just('('), just(')'))
+                .or(just('x').repeated().at_least(2).at_most(3).to('x'))
+                .labelled("expr")
+        });
+
+        let diagrams = Grammar::of(&expr).to_railroad();
+        assert_eq!(diagrams.len(), 1);
+        assert_eq!(diagrams[0].name(), "expr");
+        let svg = diagrams[0].svg();
+        assert_eq!(svg.matches("<rect class=\"rule\"").count(), 1);
+        assert_eq!(svg.matches("<rect class=\"literal\"").count(), 3);
+        assert!(svg.contains(">2 to 3 times</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn grammar_lint() {
+        // Keywords only match whole identifiers, so don't shadow longer ones
+        let kw = text::keyword::<_, _, Simple<char>>("in")
+            .or(text::keyword("int"))
+            .or(just("a").ignored())
+            .or(just("ab").ignored());
+        assert_eq!(
+            Grammar::of(&kw)
+                .lint()
+                .into_iter()
+                .map(|lint| lint.kind().clone())
+                .collect::<Vec<_>>(),
+            vec![LintKind::UnreachableAlternative {
+                alternative: 3,
+                shadowed_by: 2
+            }],
+        );
+
+        let class = one_of::<_, _, Simple<char>>("+-")
+            .ignored()
+            .or(just('-').then(just('>')).ignored())
+            .or(just('x').or_not().ignored())
+            .or(just('y').ignored());
+        let lints = Grammar::of(&class).lint();
+        assert_eq!(lints.len(), 2);
+        assert_eq!(
+            lints[0].kind(),
+            &LintKind::UnreachableAlternative {
+                alternative: 1,
+                shadowed_by: 0
+            }
+        );
+        assert_eq!(
+            lints[1].kind(),
+            &LintKind::UnreachableAlternative {
+                alternative: 3,
+                shadowed_by: 2
+            }
+        );
+
+        let empty = text::whitespace::<_, Simple<char>>().repeated();
+        let line = line!() - 1;
+        let lints = Grammar::of(&empty).lint();
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].kind(), &LintKind::EmptyLoop);
+        assert_eq!(lints[0].location().unwrap().line(), line);
+
+        // The output of `try_map` might be rejected, so it doesn't shadow later alternatives
+        let checked = just::<_, _, Simple<char>>("a")
+            .try_map(|a, span| Err(Simple::custom(span, a)))
+            .or(just("ab"));
+        assert_eq!(Grammar::of(&checked).lint(), Vec::new());
+
+        // The whitespace around a padded pattern is optional
+        let padded = primitive::empty::<Simple<char>>().padded().repeated();
+        let lints = Grammar::of(&padded).lint();
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].kind(), &LintKind::EmptyLoop);
+
+        let expr = recursive::recursive::<_, _, _, _, Simple<char>>(|expr| {
+            let atom = text::int(10).or(expr.clone().delimited_by(just('('), just(')')));
+            let sum = recursive::recursive(|sum| {
+                expr.then_ignore(just('+'))
+                    .then(sum)
+                    .map(|(a, b): (String, String)| a + &b)
+                    .or(atom)
+                    .labelled("sum")
+            });
+            sum
+        })
+        .labelled("expr");
+        let lints = Grammar::of(&expr).lint();
+        assert_eq!(lints.len(), 1);
+        assert_eq!(
+            lints[0].kind(),
+            &LintKind::LeftRecursion {
+                cycle: vec!["expr".to_string(), "sum".to_string(), "expr".to_string()]
+            }
+        );
+        assert!(lints[0].to_string().contains("expr -> sum -> expr"));
+    }
+}
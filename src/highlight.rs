@@ -0,0 +1,146 @@
+//! Syntax highlighting, classifying the input according to the parsers that parsed it.
+//!
+//! *“In the beginning the Universe was created. This has made a lot of people very angry and been widely regarded as a
+//! bad move.”*
+//!
+//! Parsers tagged with [`Parser::highlighted`] record the inputs that they parse when [`ParseConfig::highlight`] is
+//! enabled (or when using [`Parser::highlight`]). When tagged parsers are nested within one another, the innermost
+//! class applies, so the resulting ranges never overlap.
+//!
+//! Only parsers that contributed to the result are taken into account: those that were backtracked over are not. Input
+//! with syntax errors is highlighted as far as the parser got, so recovering from errors (see [`Parser::recover_with`])
+//! allows the input after an error to be highlighted too. Inputs skipped during recovery are left unclassified.
+
+use super::*;
+
+/// The class of a highlighted range of the input, such as a keyword or a string. See [`Parser::highlighted`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HighlightClass {
+    /// A keyword, such as `if` or `fn`.
+    Keyword,
+    /// The name of a variable, field or other item.
+    Identifier,
+    /// The name of a function.
+    Function,
+    /// The name of a type.
+    Type,
+    /// A constant, such as `true` or `null`.
+    Constant,
+    /// A string or character literal.
+    String,
+    /// A numeric literal.
+    Number,
+    /// A comment.
+    Comment,
+    /// An operator, such as `+` or `==`.
+    Operator,
+    /// Punctuation, such as brackets and separators.
+    Punctuation,
+    /// A class not covered by the other variants, named by the given string.
+    Custom(&'static str),
+}
+
+/// Find the non-overlapping ranges of offsets covered by the highlighted parsers recorded during a parse, in order.
+pub(crate) fn ranges(records: &[Record]) -> Vec<(Range<usize>, HighlightClass)> {
+    fn within<R: Iterator<Item = (Range<usize>, HighlightClass)>>(
+        offsets: Range<usize>,
+        class: Option<HighlightClass>,
+        records: &mut core::iter::Peekable<R>,
+        ranges: &mut Vec<(Range<usize>, HighlightClass)>,
+    ) {
+        let push = |offsets: Range<usize>, ranges: &mut Vec<_>| {
+            if let Some(class) = class.filter(|_| !offsets.is_empty()) {
+                ranges.push((offsets, class));
+            }
+        };
+        let mut pos = offsets.start;
+        while let Some((inner, _)) = records.peek() {
+            if inner.start < pos {
+                // Overlaps the previous range without nesting within it
+                records.next();
+                continue;
+            } else if inner.end > offsets.end {
+                break;
+            }
+            let (inner, inner_class) = records.next().unwrap();
+            push(pos..inner.start, ranges);
+            pos = inner.end;
+            within(inner, Some(inner_class), records, ranges);
+        }
+        push(pos..offsets.end, ranges);
+    }
+
+    let mut records = records
+        .iter()
+        .enumerate()
+        .filter_map(|(i, record)| match record.kind {
+            RecordKind::Highlight(class) if record.start < record.end => {
+                Some((record.start..record.end, i, class))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    // Records are made as parsers finish, so enclosing ranges come first when sorted by reverse order of recording
+    records.sort_by(|(a, a_idx, _), (b, b_idx, _)| {
+        a.start
+            .cmp(&b.start)
+            .then(b.end.cmp(&a.end))
+            .then(b_idx.cmp(a_idx))
+    });
+
+    let mut ranges = Vec::new();
+    let mut records = records
+        .into_iter()
+        .map(|(offsets, _, class)| (offsets, class))
+        .peekable();
+    within(0..usize::MAX, None, &mut records, &mut ranges);
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Simple, text::TextParser};
+
+    #[test]
+    fn highlighting() {
+        let escape = just::<_, _, Simple<char>>('\\')
+            .then(any())
+            .highlighted(HighlightClass::Custom("escape"));
+        let string = escape
+            .ignored()
+            .or(filter(|c: &char| *c != '"').ignored())
+            .repeated()
+            .delimited_by(just('"'), just('"'))
+            .highlighted(HighlightClass::String);
+        // `call` highlights `ident` as a function, but is backtracked over for `foo`
+        let ident = text::ident().highlighted(HighlightClass::Identifier);
+        let call = ident
+            .then_ignore(just("()"))
+            .highlighted(HighlightClass::Function);
+        let value = string.ignored().or(call.ignored()).or(ident.ignored());
+        let parser = value.padded().repeated().then_ignore(end());
+
+        let src = r#"foo "a\nb" bar()"#;
+        let output = parser.parse_recovery_with(src, ParseConfig::new().highlight());
+        assert!(output.has_output());
+        assert_eq!(
+            output.highlights(),
+            &[
+                (0..3, HighlightClass::Identifier),
+                (4..6, HighlightClass::String),
+                (6..8, HighlightClass::Custom("escape")),
+                (8..10, HighlightClass::String),
+                (11..14, HighlightClass::Identifier),
+                (14..16, HighlightClass::Function),
+            ],
+        );
+        assert_eq!(parser.highlight(src), output.highlights());
+
+        // Highlighting is disabled by default
+        assert!(parser
+            .parse_recovery_with(src, ParseConfig::new())
+            .highlights()
+            .is_empty());
+    }
+}
@@ -0,0 +1,409 @@
+//! Incremental reparsing, reusing the results of a previous parse after the input has been edited.
+//!
+//! *“For a moment, nothing happened. Then, after a second or so, nothing continued to happen.”*
+//!
+//! Parsers marked with [`Parser::memoized`] remember their results when [`ParseConfig::memoize`] is enabled. A later
+//! parse of the edited input, configured with [`ParseConfig::reparse`], reuses each result that the [`Edit`] could not
+//! have affected instead of invoking the parser again. Results that follow the edit are shifted to their new position
+//! (see [`Shift`]).
+//!
+//! A result is only reused if the parser examined no input within the edited region (including any input that it
+//! looked ahead at without consuming), so reparsing produces the same output, errors and concrete syntax tree as
+//! parsing the edited input from scratch. This relies on memoized parsers producing the same result wherever they are
+//! invoked: they must not depend on state outside of the input, such as side effects of the functions given to
+//! combinators like [`Parser::map`]. Only the parsers that are actually invoked count towards limits like
+//! [`ParseConfig::fuel`], and parsers created during parsing (such as by [`Parser::then_with`]) are never reused.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, incremental::Edit, ParseConfig};
+//! let item = text::ident::<_, Simple<char>>()
+//!     .map_with_span(|name, span| (name, span))
+//!     .padded()
+//!     .memoized();
+//! let items = item.repeated().then_ignore(end());
+//!
+//! let output = items.parse_recovery_with("foo bar baz", ParseConfig::new().memoize());
+//!
+//! // Replace `bar` (at offsets 4..7) with `quux`
+//! let edit = Edit::new(4..7, 4);
+//! let reparsed = items.parse_recovery_with("foo quux baz", ParseConfig::new().reparse(&output, edit));
+//!
+//! assert_eq!(
+//!     reparsed.into_result(),
+//!     Ok(vec![
+//!         ("foo".to_string(), 0..3),
+//!         ("quux".to_string(), 4..8),
+//!         ("baz".to_string(), 9..12),
+//!     ]),
+//! );
+//! ```
+
+use super::*;
+use alloc::collections::BTreeMap;
+use core::any::Any;
+
+/// A change to the input between two parses: the inputs at `offsets` in the old input were replaced by `len` new
+/// inputs. See [`ParseConfig::reparse`].
+///
+/// When parsing a string, offsets are given in terms of `char`s. When the input is a [`Stream`] of tokens, offsets are
+/// given in terms of tokens (not counting those hidden by [`Stream::from_iter_with_trivia`], which belong to the token
+/// that follows them), so the edit must cover every token that the change to the source touched.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Edit {
+    pub(crate) offsets: Range<usize>,
+    pub(crate) len: usize,
+    pub(crate) span_shift: Option<isize>,
+}
+
+impl Edit {
+    /// Create an edit that replaces the inputs at `offsets` with `len` new inputs.
+    ///
+    /// Spans following the edit are shifted by the change in the number of inputs, which is correct when parsing a
+    /// string with [`Range<usize>`] spans. Use [`Edit::shift_spans_by`] if spans are measured in different units.
+    pub fn new(offsets: Range<usize>, len: usize) -> Self {
+        Self {
+            offsets,
+            len,
+            span_shift: None,
+        }
+    }
+
+    /// Shift spans following the edit by the given amount, rather than by the change in the number of inputs.
+    ///
+    /// This is useful when parsing tokens with spans into the source text, where the amount that the source text grew
+    /// or shrank by should be given.
+    pub fn shift_spans_by(self, by: isize) -> Self {
+        Self {
+            span_shift: Some(by),
+            ..self
+        }
+    }
+
+    /// The amount that offsets following the edit move by.
+    fn offset_shift(&self) -> isize {
+        self.len as isize - (self.offsets.end - self.offsets.start) as isize
+    }
+}
+
+/// A value that contains spans, which must be moved when the input that it was parsed from moves. See
+/// [`Parser::memoized`].
+///
+/// This is implemented for spans, for common error types and for common containers of such values. To memoize a
+/// parser that produces your own AST, implement this trait for the AST, shifting every span within it. Values that
+/// contain no spans can implement it by doing nothing.
+///
+/// `usize` deliberately does not implement this trait: it is not possible to tell whether a `usize` is an offset into
+/// the input or an unrelated number.
+pub trait Shift {
+    /// Move every span within the value by the given amount.
+    fn shift(&mut self, by: isize);
+}
+
+pub(crate) fn shift_offset(offset: usize, by: isize) -> usize {
+    (offset as isize + by) as usize
+}
+
+impl Shift for Range<usize> {
+    fn shift(&mut self, by: isize) {
+        self.start = shift_offset(self.start, by);
+        self.end = shift_offset(self.end, by);
+    }
+}
+
+macro_rules! impl_shift_for_unspanned {
+    ($($t:ty),*) => {
+        $(impl Shift for $t {
+            fn shift(&mut self, _: isize) {}
+        })*
+    };
+}
+
+impl_shift_for_unspanned!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    String
+);
+
+impl Shift for &str {
+    fn shift(&mut self, _: isize) {}
+}
+
+impl<T: Shift> Shift for Option<T> {
+    fn shift(&mut self, by: isize) {
+        if let Some(x) = self {
+            x.shift(by);
+        }
+    }
+}
+
+impl<T: Shift, E: Shift> Shift for Result<T, E> {
+    fn shift(&mut self, by: isize) {
+        match self {
+            Ok(x) => x.shift(by),
+            Err(e) => e.shift(by),
+        }
+    }
+}
+
+impl<T: Shift + ?Sized> Shift for Box<T> {
+    fn shift(&mut self, by: isize) {
+        (**self).shift(by);
+    }
+}
+
+impl<T: Shift> Shift for Vec<T> {
+    fn shift(&mut self, by: isize) {
+        self.iter_mut().for_each(|x| x.shift(by));
+    }
+}
+
+impl<T: Shift> Shift for [T] {
+    fn shift(&mut self, by: isize) {
+        self.iter_mut().for_each(|x| x.shift(by));
+    }
+}
+
+macro_rules! impl_shift_for_tuple {
+    ($($x:ident),*) => {
+        #[allow(non_snake_case)]
+        impl<$($x: Shift),*> Shift for ($($x,)*) {
+            fn shift(&mut self, by: isize) {
+                let ($($x,)*) = self;
+                $($x.shift(by);)*
+            }
+        }
+    };
+}
+
+impl_shift_for_tuple!(A);
+impl_shift_for_tuple!(A, B);
+impl_shift_for_tuple!(A, B, C);
+impl_shift_for_tuple!(A, B, C, D);
+impl_shift_for_tuple!(A, B, C, D, E);
+impl_shift_for_tuple!(A, B, C, D, E, F);
+
+/// The results of the memoized parsers invoked during a parse, for reuse by a later parse. See
+/// [`ParseConfig::reparse`].
+#[derive(Clone)]
+pub(crate) struct Memos {
+    /// The parser that was parsed with: results are only reused when parsing with the same parser.
+    pub(crate) root: Site,
+    /// Whether the parse built a concrete syntax tree (see [`ParseConfig::cst`]).
+    pub(crate) cst: bool,
+    /// Whether the parse highlighted the input (see [`ParseConfig::highlight`]).
+    pub(crate) highlight: bool,
+    pub(crate) slots: Arc<Vec<Slot>>,
+}
+
+impl fmt::Debug for Memos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Memos")
+            .field("len", &Vec::len(&self.slots))
+            .finish()
+    }
+}
+
+impl Memos {
+    /// Find the results that can be reused after the edit, keyed by their new offset and the site of their parser,
+    /// given the sites of the memoized parsers within the parser being parsed with.
+    pub(crate) fn reusable(&self, edit: &Edit, sites: &[Site]) -> BTreeMap<(usize, Site), Slot> {
+        fn find(
+            slots: &[Slot],
+            shift: (isize, isize),
+            edit: &Edit,
+            sites: &[Site],
+            reusable: &mut BTreeMap<(usize, Site), Slot>,
+        ) {
+            for slot in slots {
+                let memo = &slot.memo;
+                let (offset_shift, span_shift) =
+                    (shift.0 + slot.offset_shift, shift.1 + slot.span_shift);
+                let start = shift_offset(memo.start, offset_shift);
+                let reach = shift_offset(memo.reach, offset_shift);
+                let shift = if reach <= edit.offsets.start {
+                    Some((offset_shift, span_shift))
+                } else if start >= edit.offsets.end {
+                    let by = edit.offset_shift();
+                    Some((
+                        offset_shift + by,
+                        span_shift + edit.span_shift.unwrap_or(by),
+                    ))
+                } else {
+                    None
+                };
+                if let Some((offset_shift, span_shift)) =
+                    shift.filter(|_| sites.contains(&memo.site))
+                {
+                    reusable.insert(
+                        (shift_offset(memo.start, offset_shift), memo.site),
+                        Slot {
+                            memo: memo.clone(),
+                            offset_shift,
+                            span_shift,
+                        },
+                    );
+                }
+                // Results within a result that cannot be reused might still be reusable themselves
+                find(
+                    &memo.nested,
+                    (offset_shift, span_shift),
+                    edit,
+                    sites,
+                    reusable,
+                );
+            }
+        }
+
+        let mut reusable = BTreeMap::new();
+        find(&self.slots, (0, 0), edit, sites, &mut reusable);
+        reusable
+    }
+}
+
+/// The result of a memoized parser that succeeded without generating errors, in the position that it was parsed at.
+pub(crate) struct Memo {
+    pub(crate) site: Site,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    /// The offset following the furthest input that the parser examined.
+    pub(crate) reach: usize,
+    /// The output of the parser along with its alternative error, if any (and the offset of that error).
+    pub(crate) output: Arc<dyn Any + Send + Sync>,
+    /// The records made by the parser (see [`ParseState::records`]).
+    pub(crate) records: Vec<Record>,
+    /// The results of the memoized parsers invoked by the parser.
+    pub(crate) nested: Vec<Slot>,
+}
+
+/// A [`Memo`], moved by the given amounts to the position that it now belongs at.
+#[derive(Clone)]
+pub(crate) struct Slot {
+    pub(crate) memo: Arc<Memo>,
+    pub(crate) offset_shift: isize,
+    pub(crate) span_shift: isize,
+}
+
+/// The state of the parse when a memoized parser was invoked. See [`Stream::memo_checkpoint`].
+pub(crate) struct MemoCheckpoint {
+    pub(crate) start: usize,
+    pub(crate) reach: usize,
+    pub(crate) records: usize,
+    pub(crate) memos: usize,
+    pub(crate) recoveries: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Simple, text::TextParser};
+
+    #[test]
+    fn incremental_reparse() {
+        use core::cell::Cell;
+
+        #[derive(Clone, Debug, PartialEq)]
+        enum Tree {
+            Leaf(String, Range<usize>),
+            List(Vec<Tree>, Range<usize>),
+        }
+
+        impl Shift for Tree {
+            fn shift(&mut self, by: isize) {
+                match self {
+                    Tree::Leaf(_, span) => span.shift(by),
+                    Tree::List(items, span) => {
+                        items.shift(by);
+                        span.shift(by);
+                    }
+                }
+            }
+        }
+
+        // The smallest edit that turns `old` into `new`
+        fn edit(old: &str, new: &str) -> Edit {
+            let old = old.chars().collect::<Vec<_>>();
+            let new = new.chars().collect::<Vec<_>>();
+            let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+            let suffix = old[prefix..]
+                .iter()
+                .rev()
+                .zip(new[prefix..].iter().rev())
+                .take_while(|(a, b)| a == b)
+                .count();
+            Edit::new(prefix..old.len() - suffix, new.len() - prefix - suffix)
+        }
+
+        let leaves = Rc::new(Cell::new(0));
+        let counter = leaves.clone();
+        let tree = recursive::recursive(|tree| {
+            let leaf = text::ident::<_, Simple<char>>().map_with_span(move |name, span| {
+                counter.set(counter.get() + 1);
+                Tree::Leaf(name, span)
+            });
+            let list = tree
+                .padded()
+                .separated_by(just(','))
+                .delimited_by(just('['), just(']'))
+                .map_with_span(Tree::List)
+                .labelled("list");
+            leaf.or(list).memoized()
+        });
+        let item = tree
+            .padded()
+            .then_ignore(just(';'))
+            .labelled("item")
+            .memoized();
+        let items = item
+            .recover_with(skip_then_retry_until([';']))
+            .repeated()
+            .then_ignore(end());
+
+        let srcs = [
+            "[a, b]; c; [d, [e, f]];",
+            "[a, b]; cc; [d, [e, f]];",
+            "[a, b]; cc; [d, [e, f]]; g;",
+            "x; [a, b]; cc; [d, [e, f]]; g;",
+            "x; [a, b; cc; [d, [e, f]]; g;",
+            "x; [a, b]; cc; [d, [e, f]]; g;",
+            "x; [a b]; cc; [d, [e, f], [h]]; g",
+            "[a, b]; c; [d, [e, f]];",
+        ];
+        let mut previous = items.parse_recovery_with(srcs[0], ParseConfig::new().cst().memoize());
+        for (i, pair) in srcs.windows(2).enumerate() {
+            let config = ParseConfig::new()
+                .cst()
+                .reparse(&previous, edit(pair[0], pair[1]));
+            leaves.set(0);
+            let reparsed = items.parse_recovery_with(pair[1], config);
+            let reparsed_leaves = leaves.get();
+            leaves.set(0);
+            let parsed = items.parse_recovery_with(pair[1], ParseConfig::new().cst());
+
+            assert_eq!(reparsed.output(), parsed.output(), "{:?}", pair);
+            assert_eq!(reparsed.errors(), parsed.errors(), "{:?}", pair);
+            assert_eq!(reparsed.cst(), parsed.cst(), "{:?}", pair);
+            assert!(reparsed_leaves <= leaves.get());
+            if i == 0 {
+                // Only `cc` needed parsing again
+                assert_eq!(reparsed_leaves, 1);
+            }
+            previous = reparsed;
+        }
+    }
+}
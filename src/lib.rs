@@ -8,26 +8,44 @@ extern crate alloc;
 
 pub mod chain;
 pub mod combinator;
+pub mod config;
+pub mod cst;
 pub mod debug;
 pub mod error;
+pub mod grammar;
+pub mod highlight;
+pub mod incremental;
 pub mod primitive;
 pub mod recovery;
 pub mod recursive;
 pub mod span;
 pub mod stream;
 pub mod text;
-
-pub use crate::{error::Error, span::Span};
+pub mod trivia;
+
+pub use crate::{
+    config::{
+        Completion, LeftRecursion, Limit, ParseConfig, ParseOutput, RecoveryEvent, StalledLoop,
+    },
+    error::Error,
+    span::Span,
+};
 
 pub use crate::stream::{BoxStream, Flat, Stream};
 
 use crate::{
     chain::Chain,
     combinator::*,
+    config::{Abandon, ParseState, Record, RecordKind, RecoveryRecord, Region},
+    cst::{CstNode, Site},
     debug::*,
     error::{merge_alts, Located},
+    grammar::{Describer, Kind, Node},
+    highlight::HighlightClass,
+    incremental::{Edit, Memo, Memos, Shift, Slot},
     primitive::*,
     recovery::*,
+    trivia::Trivia,
 };
 
 use alloc::{boxed::Box, rc::Rc, string::String, sync::Arc, vec, vec::Vec};
@@ -60,7 +78,11 @@ pub mod prelude {
             any, choice, empty, end, filter, filter_map, just, none_of, one_of, seq, take_until,
             todo,
         },
-        recovery::{nested_delimiters, skip_then_retry_until, skip_until},
+        recovery::{
+            nested_delimiters, nested_delimiters_any, skip_then_retry_until,
+            skip_then_retry_until_if, skip_then_retry_until_parser, skip_until, skip_until_if,
+            skip_until_parser,
+        },
         recursive::{recursive, Recursive},
         select,
         span::Span as _,
@@ -89,6 +111,9 @@ type PResult<I, O, E> = (
 // Shorthand for a stream with the given input and error type.
 type StreamOf<'a, I, E> = Stream<'a, I, <E as Error<I>>::Span>;
 
+// A function deciding whether two errors are duplicates of one another, used by [`ParseConfig::merge_errors`].
+type SameError<E> = fn(&E, &E) -> bool;
+
 // [`Parser::parse_recovery`], but generic across the debugger.
 fn parse_recovery_inner<
     'a,
@@ -102,20 +127,128 @@ fn parse_recovery_inner<
     parser: &P,
     debugger: &mut D,
     stream: S,
-) -> (Option<O>, Vec<P::Error>)
+    config: ParseConfig,
+    same_error: Option<SameError<P::Error>>,
+) -> ParseOutput<I, O, P::Error>
 where
     P: Sized,
 {
+    let mut stream = stream.into();
+    let stream: &mut StreamOf<I, P::Error> = &mut stream;
+    stream.state.config = config;
+    if let Some((memos, edit)) = &stream.state.config.reparse {
+        let config = &stream.state.config;
+        if memos.root == cst::site(parser)
+            && memos.cst == config.cst
+            && memos.highlight == config.highlight
+        {
+            let sites = Describer::memoized_sites(parser);
+            stream.state.reusable = memos.reusable(edit, &sites);
+        }
+    }
     #[allow(deprecated)]
-    let (mut errors, res) = parser.parse_inner(debugger, &mut stream.into());
-    let out = match res {
+    let (mut errors, res) = debugger.invoke(parser, stream);
+    let mut output = match res {
         Ok((out, _)) => Some(out),
         Err(err) => {
             errors.push(err);
             None
         }
     };
-    (out, errors.into_iter().map(|e| e.error).collect())
+
+    // The parse was abandoned, so any output and other errors are incomplete
+    if let Some((reason, at)) = stream.state.abandoned {
+        let span = stream.empty_span_at(at);
+        output = None;
+        errors = vec![Located::at(at, reason.error(span))];
+    }
+
+    // Only report recoveries whose errors weren't discarded during parsing (by backtracking, for example)
+    let regions = errors
+        .iter()
+        .filter_map(|e| e.region.map(|r| r.id))
+        .collect::<Vec<_>>();
+    let errors = stream.state.config.finish(errors, same_error);
+    let recoveries = core::mem::take(&mut stream.state.log)
+        .into_iter()
+        .filter(|r| regions.contains(&r.id))
+        .map(|r| RecoveryEvent {
+            strategy: r.strategy,
+            location: r.location,
+            span: stream.span_between(r.start, r.end),
+            skipped: r.end - r.start,
+            errors: errors
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| matches!(e.region, Some(region) if region.id == r.id))
+                .map(|(i, _)| i)
+                .collect(),
+        })
+        .collect();
+
+    // If every error at the furthest position was caused by reaching the end of the input, more input might allow the
+    // parse to succeed
+    let furthest = errors.iter().map(|e| e.at).max();
+    let incomplete = match furthest {
+        Some(at) if stream.state.abandoned.is_none() && stream.is_eoi(at) => {
+            let at_end = errors
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| e.at == at)
+                .collect::<Vec<_>>();
+            if at_end.iter().all(|(_, e)| e.error.is_unexpected_end()) {
+                Some(at_end.into_iter().map(|(i, _)| i).collect())
+            } else {
+                None
+            }
+        }
+        _ => None,
+    };
+
+    // Highlighting is useful even if the parse failed, so highlight as much of the input as possible
+    let highlights = if stream.state.config.highlight {
+        highlight::ranges(&stream.state.records)
+            .into_iter()
+            .map(|(offsets, class)| (stream.span_between(offsets.start, offsets.end), class))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let cst = if stream.state.config.cst && output.is_some() {
+        let records = core::mem::take(&mut stream.state.records);
+        let sites = Describer::sites(parser);
+        let (inputs, hidden, eoi) = stream.inputs();
+        Some(cst::build(records, &sites, inputs, hidden, eoi.clone()))
+    } else {
+        None
+    };
+
+    let memos = if stream.state.config.memoize {
+        Some(Memos {
+            root: cst::site(parser),
+            cst: stream.state.config.cst,
+            highlight: stream.state.config.highlight,
+            slots: Arc::new(core::mem::take(&mut stream.state.memos)),
+        })
+    } else {
+        None
+    };
+
+    ParseOutput {
+        output,
+        errors: errors.into_iter().map(|e| e.error).collect(),
+        recoveries,
+        trace: None,
+        profile: None,
+        explanation: None,
+        abandoned: stream.state.abandoned.map(|(reason, _)| reason),
+        incomplete,
+        cst,
+        highlights,
+        memos,
+        phantom: PhantomData,
+    }
 }
 
 /// A trait implemented by parsers.
@@ -185,6 +318,15 @@ pub trait Parser<I: Clone, O> {
         s: &mut StreamOf<I, Self::Error>,
     ) -> PResult<I, O, Self::Error>;
 
+    /// Describe the structure of this parser, for use by tools that inspect grammars. Most users will want
+    /// [`Grammar::of`](grammar::Grammar::of) instead.
+    ///
+    /// Parsers that do not override this method are described as [`Kind::Opaque`], named after their type.
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        let _ = describer;
+        Node::new(debug::short_type_name::<Self>(), Kind::Opaque)
+    }
+
     /// Parse a stream of tokens, yielding an output if possible, and any errors encountered along the way.
     ///
     /// If `None` is returned (i.e: parsing failed) then there will *always* be at least one item in the error `Vec`.
@@ -198,11 +340,78 @@ pub trait Parser<I: Clone, O> {
         Iter: Iterator<Item = (I, <Self::Error as Error<I>>::Span)> + 'a,
         S: Into<Stream<'a, I, <Self::Error as Error<I>>::Span, Iter>>,
     {
-        parse_recovery_inner(self, &mut Silent::new(), stream)
+        parse_recovery_inner(
+            self,
+            &mut Silent::new(),
+            stream,
+            ParseConfig::default(),
+            None,
+        )
+        .into_output_errors()
+    }
+
+    /// Parse a stream of tokens, yielding an output if possible, and any errors encountered along the way, using the
+    /// given [`ParseConfig`] to control how errors are collected and reported.
+    ///
+    /// If the output is `None` (i.e: parsing failed) then there will *always* be at least one error.
+    ///
+    /// Although the signature of this function looks complicated, it's simpler than you think! You can pass a
+    /// `&[I]`, a [`&str`], or a [`Stream`] to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, ParseConfig};
+    /// let list = just::<_, _, Simple<char>>('a')
+    ///     .recover_with(skip_then_retry_until([',']))
+    ///     .separated_by(just(','));
+    ///
+    /// let output = list.parse_recovery_with("a,2a,3a,a", ParseConfig::new());
+    /// assert_eq!(output.output(), Some(&vec!['a', 'a', 'a', 'a']));
+    /// assert_eq!(output.errors().len(), 2);
+    ///
+    /// // Stop recovering after the first error
+    /// let output = list.parse_recovery_with("a,2a,3a,a", ParseConfig::new().max_errors(1));
+    /// assert_eq!(output.output(), Some(&vec!['a', 'a']));
+    /// assert_eq!(output.errors().len(), 1);
+    /// ```
+    fn parse_recovery_with<'a, Iter, S>(
+        &self,
+        stream: S,
+        config: ParseConfig,
+    ) -> ParseOutput<I, O, Self::Error>
+    where
+        Self: Sized,
+        Self::Error: PartialEq,
+        Iter: Iterator<Item = (I, <Self::Error as Error<I>>::Span)> + 'a,
+        S: Into<Stream<'a, I, <Self::Error as Error<I>>::Span, Iter>>,
+    {
+        if config.trace || config.profile || config.explain {
+            let (trace, explain) = (config.trace, config.explain);
+            let mut debugger = Verbose::new(trace || explain, config.profile);
+            let mut output =
+                parse_recovery_inner(self, &mut debugger, stream, config, Some(PartialEq::eq));
+            let (full_trace, profile) = debugger.finish();
+            if explain && output.has_errors() {
+                output.explanation = full_trace.as_ref().and_then(Explanation::from_trace);
+            }
+            output.trace = full_trace.filter(|_| trace);
+            output.profile = profile;
+            output
+        } else {
+            parse_recovery_inner(
+                self,
+                &mut Silent::new(),
+                stream,
+                config,
+                Some(PartialEq::eq),
+            )
+        }
     }
 
     /// Parse a stream of tokens, yielding an output if possible, and any errors encountered along the way. Unlike
-    /// [`Parser::parse_recovery`], this function will produce verbose debugging output as it executes.
+    /// [`Parser::parse_recovery`], this function will produce verbose debugging output as it executes. To record a
+    /// [`Trace`] of every parser invocation, use [`ParseConfig::trace`].
     ///
     /// If `None` is returned (i.e: parsing failed) then there will *always* be at least one item in the error `Vec`.
     /// If you don't care about producing an output if errors are encountered, use `Parser::parse` instead.
@@ -220,10 +429,10 @@ pub trait Parser<I: Clone, O> {
         Iter: Iterator<Item = (I, <Self::Error as Error<I>>::Span)> + 'a,
         S: Into<Stream<'a, I, <Self::Error as Error<I>>::Span, Iter>>,
     {
-        let mut debugger = Verbose::new();
-        let res = parse_recovery_inner(self, &mut debugger, stream);
+        let mut debugger = Verbose::new(true, false);
+        let res = parse_recovery_inner(self, &mut debugger, stream, ParseConfig::default(), None);
         debugger.print();
-        res
+        res.into_output_errors()
     }
 
     /// Parse a stream of tokens, yielding an output *or* any errors that were encountered along the way.
@@ -248,7 +457,148 @@ pub trait Parser<I: Clone, O> {
         }
     }
 
-    /// Include this parser in the debugging output produced by [`Parser::parse_recovery_verbose`].
+    /// Find the inputs that could come next at the given offset (such as a cursor position in an editor), for the sake
+    /// of suggesting completions.
+    ///
+    /// The input is parsed up to the offset, as if the input ended there, recovering from any errors along the way.
+    /// The errors generated at the offset (including those of alternatives that were not taken) are merged together,
+    /// describing the inputs that were expected there and the label of the innermost labelled parser (as tracked by
+    /// [`Error::expected_input_found`] and [`Parser::labelled`]). The labels of every labelled parser enclosing the
+    /// offset are also reported, innermost first.
+    ///
+    /// The offset is measured in inputs (such as `char`s, when parsing a string), not bytes.
+    ///
+    /// Although the signature of this function looks complicated, it's simpler than you think! You can pass a
+    /// [`&[I]`], a [`&str`], or a [`Stream`] to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let value = just::<_, _, Simple<char>>("true")
+    ///     .or(just("false"))
+    ///     .labelled("value");
+    /// let pair = text::ident()
+    ///     .then_ignore(just('=').padded())
+    ///     .then(value)
+    ///     .labelled("pair");
+    /// let pairs = pair.separated_by(just(',').padded()).then_ignore(end());
+    ///
+    /// let completion = pairs.expected_at("a = true, b = ", 14);
+    /// let mut expected = completion.error().unwrap().expected().copied().collect::<Vec<_>>();
+    /// expected.sort();
+    /// assert_eq!(expected, vec![Some('f'), Some('t')]);
+    /// assert_eq!(completion.contexts(), &["value", "pair"]);
+    ///
+    /// // Anything after the offset is ignored
+    /// let completion = pairs.expected_at("a = true, b = }}", 14);
+    /// assert_eq!(completion.contexts(), &["value", "pair"]);
+    /// ```
+    fn expected_at<'a, Iter, S>(&self, stream: S, offset: usize) -> Completion<I, Self::Error>
+    where
+        Self: Sized,
+        Iter: Iterator<Item = (I, <Self::Error as Error<I>>::Span)> + 'a,
+        S: Into<Stream<'a, I, <Self::Error as Error<I>>::Span, Iter>>,
+        <Self::Error as Error<I>>::Label: PartialEq,
+    {
+        let mut stream = stream.into();
+        let stream: &mut StreamOf<I, Self::Error> = &mut stream;
+        let (inputs, eoi) = stream.prefix(offset);
+        let offset = inputs.len();
+
+        let mut stream = Stream::from_iter(eoi, inputs.into_iter());
+        let stream: &mut StreamOf<I, Self::Error> = &mut stream;
+        stream.state.completing = true;
+        #[allow(deprecated)]
+        let (errors, res) = Silent::new().invoke(self, stream);
+        let alt = match res {
+            Ok((_, alt)) => alt,
+            Err(err) => Some(err),
+        };
+
+        let located = merge_alts(
+            None,
+            errors.into_iter().chain(alt).filter(|e| e.at == offset),
+        );
+        let (error, contexts) = match located {
+            Some(located) => {
+                let mut contexts = Vec::new();
+                for context in located.contexts {
+                    if !contexts.contains(&context) {
+                        contexts.push(context);
+                    }
+                }
+                (Some(located.error), contexts)
+            }
+            None => (None, Vec::new()),
+        };
+        Completion {
+            error,
+            contexts,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Classify a stream of inputs for the sake of syntax highlighting, returning the span and class of each range of
+    /// the input parsed by a parser tagged with [`Parser::highlighted`], in order.
+    ///
+    /// The ranges never overlap. Input with syntax errors is highlighted as far as the parser got, so configure
+    /// recovery (see [`Parser::recover_with`]) to highlight the input following an error. To produce an output
+    /// alongside the highlighting, use [`ParseConfig::highlight`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, highlight::HighlightClass::{Identifier, Keyword, Number, Operator, Punctuation}};
+    /// let stmt = text::keyword::<_, _, Simple<char>>("let")
+    ///     .highlighted(Keyword)
+    ///     .ignore_then(text::ident().highlighted(Identifier).padded())
+    ///     .then_ignore(just('=').highlighted(Operator))
+    ///     .then(text::int(10).highlighted(Number).padded())
+    ///     .then_ignore(just(';').highlighted(Punctuation))
+    ///     .padded()
+    ///     .recover_with(skip_until([';'], |_| (String::new(), String::new())).consume_end());
+    /// let stmts = stmt.repeated().then_ignore(end());
+    ///
+    /// // The second statement is missing its name, so is skipped, but the third is still highlighted
+    /// let highlights = stmts.highlight("let x = 1; let = 2; let z = 3;");
+    /// assert_eq!(
+    ///     highlights,
+    ///     vec![
+    ///         (0..3, Keyword),
+    ///         (4..5, Identifier),
+    ///         (6..7, Operator),
+    ///         (8..9, Number),
+    ///         (9..10, Punctuation),
+    ///         (20..23, Keyword),
+    ///         (24..25, Identifier),
+    ///         (26..27, Operator),
+    ///         (28..29, Number),
+    ///         (29..30, Punctuation),
+    ///     ],
+    /// );
+    /// ```
+    fn highlight<'a, Iter, S>(
+        &self,
+        stream: S,
+    ) -> Vec<(<Self::Error as Error<I>>::Span, HighlightClass)>
+    where
+        Self: Sized,
+        Iter: Iterator<Item = (I, <Self::Error as Error<I>>::Span)> + 'a,
+        S: Into<Stream<'a, I, <Self::Error as Error<I>>::Span, Iter>>,
+    {
+        parse_recovery_inner(
+            self,
+            &mut Silent::new(),
+            stream,
+            ParseConfig::new().highlight(),
+            None,
+        )
+        .highlights
+    }
+
+    /// Label this parser in the debugging output produced by [`Parser::parse_recovery_verbose`] and
+    /// [`ParseConfig::trace`], and include it as a site in the report produced by [`ParseConfig::profile`].
     ///
     /// You'll probably want to make sure that this doesn't end up in production code: it exists only to help you debug
     /// your parser. Additionally, its API is quite likely to change in future versions.
@@ -329,6 +679,120 @@ pub trait Parser<I: Clone, O> {
         MapWithSpan(self, f, PhantomData)
     }
 
+    /// Map the output of this parser to another value, making use of the pattern's span and whether any part of the
+    /// pattern was recovered from when doing so.
+    ///
+    /// Outputs generated by error recovery (such as the fallback of [`nested_delimiters`]) are otherwise
+    /// indistinguishable from real outputs. This combinator allows such outputs to be marked so that later stages of
+    /// compilation (type-checking, for example) can avoid generating spurious errors for them.
+    ///
+    /// The output type of this parser is `U`, the same as the function's output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Debug, PartialEq)]
+    /// enum Expr {
+    ///     Num(String),
+    ///     List(Vec<Expr>),
+    ///     Error,
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Node {
+    ///     expr: Expr,
+    ///     poisoned: bool,
+    /// }
+    ///
+    /// let list = text::int::<_, Simple<char>>(10)
+    ///     .map(Expr::Num)
+    ///     .padded()
+    ///     .separated_by(just(','))
+    ///     .delimited_by(just('['), just(']'))
+    ///     .map(Expr::List)
+    ///     .recover_with(nested_delimiters('[', ']', [], |_| Expr::Error))
+    ///     .map_recovered(|expr, poisoned, _span| Node { expr, poisoned });
+    ///
+    /// assert_eq!(list.parse("[1, 2]").unwrap().poisoned, false);
+    ///
+    /// let (node, errors) = list.parse_recovery("[1, +]");
+    /// assert_eq!(node, Some(Node { expr: Expr::Error, poisoned: true }));
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    fn map_recovered<U, F>(self, f: F) -> MapRecovered<Self, F, O>
+    where
+        Self: Sized,
+        F: Fn(O, bool, <Self::Error as Error<I>>::Span) -> U,
+    {
+        MapRecovered(self, f, PhantomData)
+    }
+
+    /// Map the output of this parser to another value, making use of the trivia (such as comments) at either end of
+    /// the pattern when doing so.
+    ///
+    /// Trivia is the input consumed by parsers marked with [`Parser::record_trivia`]. The leading trivia is the
+    /// unbroken run of trivia at the start of the pattern and the trailing trivia is the unbroken run at its end, so
+    /// trivia is only found if it was parsed as part of the pattern: attach comments to the node that follows them by
+    /// parsing them before the node (such as with [`Parser::ignore_then`]) rather than after the node that precedes
+    /// them.
+    ///
+    /// The output type of this parser is `U`, the same as the function's output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Debug, PartialEq)]
+    /// struct Item {
+    ///     name: String,
+    ///     docs: Vec<String>,
+    /// }
+    ///
+    /// let comment = just::<_, _, Simple<char>>("//")
+    ///     .then(take_until(text::newline()))
+    ///     .ignored()
+    ///     .record_trivia();
+    /// let whitespace = filter(|c: &char| c.is_whitespace())
+    ///     .repeated()
+    ///     .at_least(1)
+    ///     .ignored()
+    ///     .record_trivia();
+    /// let trivia = comment.or(whitespace).repeated();
+    ///
+    /// let item = trivia
+    ///     .clone()
+    ///     .ignore_then(text::keyword("fn"))
+    ///     .ignore_then(text::ident().padded())
+    ///     .then_ignore(just(';'))
+    ///     .map_with_trivia(|name, trivia| Item {
+    ///         name,
+    ///         docs: trivia
+    ///             .leading()
+    ///             .iter()
+    ///             .map(|(c, _)| c.iter().collect::<String>())
+    ///             .filter(|c| c.starts_with("///"))
+    ///             .collect(),
+    ///     });
+    ///
+    /// let items = item.repeated().then_ignore(trivia).then_ignore(end());
+    ///
+    /// assert_eq!(
+    ///     items.parse("/// Does a thing.\nfn a;\n\n// Not a doc comment\nfn b;\n"),
+    ///     Ok(vec![
+    ///         Item { name: "a".to_string(), docs: vec!["/// Does a thing.\n".to_string()] },
+    ///         Item { name: "b".to_string(), docs: vec![] },
+    ///     ]),
+    /// );
+    /// ```
+    fn map_with_trivia<U, F>(self, f: F) -> MapWithTrivia<Self, F, O>
+    where
+        Self: Sized,
+        F: Fn(O, Trivia<I, <Self::Error as Error<I>>::Span>) -> U,
+    {
+        MapWithTrivia(self, f, PhantomData)
+    }
+
     /// Map the primary error of this parser to another value.
     ///
     /// This function is most useful when using a custom error type, allowing you to augment errors according to
@@ -475,6 +939,76 @@ pub trait Parser<I: Clone, O> {
         Label(self, label)
     }
 
+    /// Memoize the results of this parser, so that parsing the input again after an edit can reuse them rather than
+    /// invoking the parser again (see [`ParseConfig::memoize`] and [`ParseConfig::reparse`]).
+    ///
+    /// Results are only remembered if the parser succeeded without generating any errors. Memoizing parsers that
+    /// parse large, self-contained regions of the input (such as items or statements) works best: each memoized parser
+    /// has a small cost, even when it is not being reused. See the [`incremental`] module for more information.
+    ///
+    /// The output and errors of the parser must implement [`Shift`], so that the spans within them can be moved when
+    /// reused after an edit that moved their input.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, incremental::{Edit, Shift}, ParseConfig};
+    /// # use std::ops::Range;
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Item {
+    ///     name: String,
+    ///     span: Range<usize>,
+    /// }
+    ///
+    /// impl Shift for Item {
+    ///     fn shift(&mut self, by: isize) {
+    ///         self.span.shift(by);
+    ///     }
+    /// }
+    ///
+    /// let item = text::keyword::<_, _, Simple<char>>("fn")
+    ///     .ignore_then(text::ident().padded())
+    ///     .then_ignore(just(';'))
+    ///     .map_with_span(|name, span| Item { name, span })
+    ///     .padded()
+    ///     .memoized();
+    /// let items = item.repeated().then_ignore(end());
+    ///
+    /// let output = items.parse_recovery_with("fn a; fn b;", ParseConfig::new().memoize());
+    ///
+    /// // Insert a new item at the start
+    /// let src = "fn new; fn a; fn b;";
+    /// let edit = Edit::new(0..0, 8);
+    /// let reparsed = items.parse_recovery_with(src, ParseConfig::new().reparse(&output, edit));
+    ///
+    /// // Reparsing gives the same result as parsing from scratch
+    /// assert_eq!(reparsed.into_result(), items.parse(src));
+    /// ```
+    fn memoized(self) -> Memoized<Self, O>
+    where
+        Self: Sized,
+        O: Clone + Shift + Send + Sync + 'static,
+        Self::Error: Clone + Shift + Send + Sync + 'static,
+    {
+        Memoized(self, PhantomData)
+    }
+
+    /// Tag the input parsed by this parser with a class for the sake of syntax highlighting (see [`Parser::highlight`]
+    /// and [`ParseConfig::highlight`]).
+    ///
+    /// When tagged parsers are nested within one another, the innermost class applies. See the [`highlight`] module
+    /// for more information.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    fn highlighted(self, class: HighlightClass) -> Highlighted<Self>
+    where
+        Self: Sized,
+    {
+        Highlighted(self, class)
+    }
+
     /// Transform all outputs of this parser to a pretermined value.
     ///
     /// The output type of this parser is `U`, the type of the predetermined value.
@@ -816,6 +1350,20 @@ pub trait Parser<I: Clone, O> {
         other.clone().ignore_then(self).then_ignore(other)
     }
 
+    /// Mark this parser as parsing trivia (such as comments or whitespace), recording the inputs that it consumes so
+    /// that they can be found by [`Parser::map_with_trivia`].
+    ///
+    /// Only invocations that consume input and contribute to the final parse are recorded: trivia parsed by a pattern
+    /// that is later backtracked over is discarded.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    fn record_trivia(self) -> RecordTrivia<Self>
+    where
+        Self: Sized,
+    {
+        RecordTrivia(self)
+    }
+
     /// Parse the pattern surrounded by the given delimiters.
     ///
     /// The output type of this parser is `O`, the same as the original parser.
@@ -879,6 +1427,63 @@ pub trait Parser<I: Clone, O> {
         }
     }
 
+    /// Parse a pattern, but with an instance of another pattern on either end, yielding the output of the inner.
+    ///
+    /// Unlike [`Parser::delimited_by`], the delimiters are single inputs. When the closing delimiter is missing at the
+    /// end of the input, an [`Error::unclosed_delimiter`] error is generated that points back at the opening delimiter,
+    /// allowing diagnostics to show where the unclosed section began.
+    ///
+    /// Optionally, the parser can recover from errors within the delimiters by skipping to the matching closing
+    /// delimiter with [`DelimitedByBalanced::with_fallback`]. Other delimiter pairs whose nesting should be respected
+    /// while doing so can be specified with [`DelimitedByBalanced::with_others`].
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, error::SimpleReason};
+    /// let list = text::int::<_, Simple<char>>(10)
+    ///     .padded()
+    ///     .separated_by(just(','))
+    ///     .delimited_by_balanced('[', ']');
+    ///
+    /// assert_eq!(list.parse("[1, 2, 3]"), Ok(vec!["1".to_string(), "2".to_string(), "3".to_string()]));
+    ///
+    /// // The error points back at the unclosed delimiter
+    /// let errors = list.parse("[1, 2, 3").unwrap_err();
+    /// assert_eq!(errors[0].reason(), &SimpleReason::Unclosed { span: 0..1, delimiter: '[' });
+    ///
+    /// // Recover by skipping to the matching closing delimiter, respecting nested parentheses
+    /// let list = list
+    ///     .with_others([('(', ')')])
+    ///     .with_fallback(|_| Vec::new())
+    ///     .then_ignore(end());
+    ///
+    /// let (list, errors) = list.parse_recovery("[1, (2]), 3]");
+    /// assert_eq!(list, Some(Vec::new()));
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    #[track_caller]
+    fn delimited_by_balanced(
+        self,
+        start: I,
+        end: I,
+    ) -> DelimitedByBalanced<Self, I, fn(<Self::Error as Error<I>>::Span) -> O, 0>
+    where
+        Self: Sized,
+        I: PartialEq,
+    {
+        DelimitedByBalanced {
+            item: self,
+            start,
+            end,
+            others: [],
+            fallback: None,
+            location: *Location::caller(),
+        }
+    }
+
     /// Parse one thing or, on failure, another thing.
     ///
     /// The output of both parsers must be of the same type, because either output can be produced.
@@ -909,12 +1514,13 @@ pub trait Parser<I: Clone, O> {
     /// assert_eq!(op.parse("/"), Ok('/'));
     /// assert!(op.parse("!").is_err());
     /// ```
+    #[track_caller]
     fn or<P>(self, other: P) -> Or<Self, P>
     where
         Self: Sized,
         P: Parser<I, O, Error = Self::Error>,
     {
-        Or(self, other)
+        Or(self, other, *Location::caller())
     }
 
     /// Apply a fallback recovery strategy to this parser should it fail.
@@ -961,12 +1567,13 @@ pub trait Parser<I: Clone, O> {
     /// // Additionally, the AST we get back still has useful information.
     /// assert_eq!(ast, Some(Expr::List(vec![Expr::Error, Expr::Error])));
     /// ```
+    #[track_caller]
     fn recover_with<S>(self, strategy: S) -> Recovery<Self, S>
     where
         Self: Sized,
         S: Strategy<I, O, Self::Error>,
     {
-        Recovery(self, strategy)
+        Recovery(self, strategy, *Location::caller())
     }
 
     /// Attempt to parse something, but only if it exists.
@@ -1076,11 +1683,12 @@ pub trait Parser<I: Clone, O> {
     ///
     /// assert_eq!(sum.parse("2+13+4+0+5"), Ok(24));
     /// ```
+    #[track_caller]
     fn repeated(self) -> Repeated<Self>
     where
         Self: Sized,
     {
-        Repeated(self, 0, None)
+        Repeated(self, 0, None, (), *Location::caller())
     }
 
     /// Parse a pattern, separated by another, any number of times.
@@ -1103,6 +1711,7 @@ pub trait Parser<I: Clone, O> {
     /// ```
     ///
     /// See [`SeparatedBy::allow_leading`] and [`SeparatedBy::allow_trailing`] for more examples.
+    #[track_caller]
     fn separated_by<U, P>(self, other: P) -> SeparatedBy<Self, P, U>
     where
         Self: Sized,
@@ -1115,6 +1724,8 @@ pub trait Parser<I: Clone, O> {
             at_most: None,
             allow_leading: false,
             allow_trailing: false,
+            recovery: (),
+            location: *Location::caller(),
             phantom: PhantomData,
         }
     }
@@ -1245,6 +1856,10 @@ impl<'a, I: Clone, O, T: Parser<I, O> + ?Sized> Parser<I, O> for &'a T {
         debugger.invoke::<_, _, T>(*self, stream)
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        (**self).describe(describer)
+    }
+
     fn parse_inner_verbose(
         &self,
         d: &mut Verbose,
@@ -1274,6 +1889,10 @@ impl<I: Clone, O, T: Parser<I, O> + ?Sized> Parser<I, O> for Box<T> {
         debugger.invoke::<_, _, T>(&*self, stream)
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        (**self).describe(describer)
+    }
+
     fn parse_inner_verbose(
         &self,
         d: &mut Verbose,
@@ -1303,6 +1922,10 @@ impl<I: Clone, O, T: Parser<I, O> + ?Sized> Parser<I, O> for Rc<T> {
         debugger.invoke::<_, _, T>(&*self, stream)
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        (**self).describe(describer)
+    }
+
     fn parse_inner_verbose(
         &self,
         d: &mut Verbose,
@@ -1332,6 +1955,10 @@ impl<I: Clone, O, T: Parser<I, O> + ?Sized> Parser<I, O> for Arc<T> {
         debugger.invoke::<_, _, T>(&*self, stream)
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        (**self).describe(describer)
+    }
+
     fn parse_inner_verbose(
         &self,
         d: &mut Verbose,
@@ -1381,6 +2008,10 @@ impl<'a, I: Clone, O, E: Error<I>> Parser<I, O> for BoxedParser<'a, I, O, E> {
         debugger.invoke(&self.0, stream)
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        self.0.describe(describer)
+    }
+
     fn parse_inner_verbose(
         &self,
         d: &mut Verbose,
@@ -40,6 +40,11 @@ impl<I: Clone, O, F: Fn(&mut StreamOf<I, E>) -> PResult<I, O, E>, E: Error<I>> P
         (self.0)(stream)
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        let _ = describer;
+        Node::new("custom", Kind::Opaque)
+    }
+
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
         #[allow(deprecated)]
         self.parse_inner(d, s)
@@ -90,6 +95,11 @@ impl<I: Clone, E: Error<I>> Parser<I, ()> for End<E> {
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        let _ = describer;
+        Node::new("end", Kind::End)
+    }
+
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, (), E> {
         #[allow(deprecated)]
         self.parse_inner(d, s)
@@ -317,6 +327,11 @@ impl<I: Clone + PartialEq, C: OrderedContainer<I> + Clone, E: Error<I>> Parser<I
         (Vec::new(), Ok((self.0.clone(), None)))
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        let _ = describer;
+        Node::new("just", Kind::Just(self.0.get_iter().collect()))
+    }
+
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, C, E> {
         #[allow(deprecated)]
         self.parse_inner(d, s)
@@ -384,6 +399,11 @@ impl<I: Clone + PartialEq, E: Error<I>> Parser<I, ()> for Seq<I, E> {
         (Vec::new(), Ok(((), None)))
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        let _ = describer;
+        Node::new("seq", Kind::Just(self.0.clone()))
+    }
+
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, (), E> {
         #[allow(deprecated)]
         self.parse_inner(d, s)
@@ -454,6 +474,11 @@ impl<I: Clone + PartialEq, C: Container<I>, E: Error<I>> Parser<I, I> for OneOf<
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        let _ = describer;
+        Node::new("one_of", Kind::OneOf(self.0.get_iter().collect()))
+    }
+
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, I, E> {
         #[allow(deprecated)]
         self.parse_inner(d, s)
@@ -505,6 +530,11 @@ impl<I: Clone, E: Error<I>> Parser<I, ()> for Empty<E> {
         (Vec::new(), Ok(((), None)))
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        let _ = describer;
+        Node::new("empty", Kind::Empty)
+    }
+
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, (), E> {
         #[allow(deprecated)]
         self.parse_inner(d, s)
@@ -522,6 +552,63 @@ pub fn empty<E>() -> Empty<E> {
     Empty(PhantomData)
 }
 
+/// See [`trivia_before`].
+#[must_use]
+pub struct TriviaBefore<E>(PhantomData<E>);
+
+impl<E> Clone for TriviaBefore<E> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<I: Clone, E: Error<I>> Parser<I, Vec<(I, E::Span)>> for TriviaBefore<E> {
+    type Error = E;
+
+    fn parse_inner<D: Debugger>(
+        &self,
+        _debugger: &mut D,
+        stream: &mut StreamOf<I, E>,
+    ) -> PResult<I, Vec<(I, E::Span)>, E> {
+        let offset = stream.offset();
+        let trivia = stream.hidden_before(offset).cloned().collect();
+        (Vec::new(), Ok((trivia, None)))
+    }
+
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        let _ = describer;
+        Node::new("trivia_before", Kind::Empty)
+    }
+
+    fn parse_inner_verbose(
+        &self,
+        d: &mut Verbose,
+        s: &mut StreamOf<I, E>,
+    ) -> PResult<I, Vec<(I, E::Span)>, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+    fn parse_inner_silent(
+        &self,
+        d: &mut Silent,
+        s: &mut StreamOf<I, E>,
+    ) -> PResult<I, Vec<(I, E::Span)>, E> {
+        #[allow(deprecated)]
+        self.parse_inner(d, s)
+    }
+}
+
+/// A parser that parses no inputs, producing the trivia hidden between the previous input and the next input, along
+/// with their spans (see [`Stream::from_iter_with_trivia`]).
+///
+/// This is most useful for extracting doc comments from the trivia that precedes an item. When the stream hides no
+/// inputs, the output is always empty.
+///
+/// The output type of this parser is `Vec<(I, E::Span)>`.
+pub fn trivia_before<I, E: Error<I>>() -> TriviaBefore<E> {
+    TriviaBefore(PhantomData)
+}
+
 /// See [`none_of`].
 #[must_use]
 pub struct NoneOf<I, C, E>(C, PhantomData<(I, E)>);
@@ -554,6 +641,11 @@ impl<I: Clone + PartialEq, C: Container<I>, E: Error<I>> Parser<I, I> for NoneOf
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        let _ = describer;
+        Node::new("none_of", Kind::NoneOf(self.0.get_iter().collect()))
+    }
+
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, I, E> {
         #[allow(deprecated)]
         self.parse_inner(d, s)
@@ -613,6 +705,7 @@ impl<I: Clone, O, A: Parser<I, O>> Parser<I, (Vec<I>, O)> for TakeUntil<A> {
                 (errors, Err(err)) => (errors, err),
             };
 
+            // Every iteration consumes an input, so unlike `Repeated` this can never stall
             match stream.next() {
                 (_, _, Some(tok)) => outputs.push(tok),
                 (_, _, None) => break (errors, Err(err)),
@@ -622,6 +715,13 @@ impl<I: Clone, O, A: Parser<I, O>> Parser<I, (Vec<I>, O)> for TakeUntil<A> {
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        Node::new(
+            "take_until",
+            Kind::TakeUntil(Box::new(self.0.describe(describer))),
+        )
+    }
+
     fn parse_inner_verbose(
         &self,
         d: &mut Verbose,
@@ -713,6 +813,11 @@ impl<I: Clone, F: Fn(&I) -> bool, E: Error<I>> Parser<I, I> for Filter<F, E> {
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        let _ = describer;
+        Node::new("filter", Kind::Filter)
+    }
+
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, I, E> {
         #[allow(deprecated)]
         self.parse_inner(d, s)
@@ -776,6 +881,11 @@ impl<I: Clone, O, F: Fn(E::Span, I) -> Result<O, E>, E: Error<I>> Parser<I, O> f
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        let _ = describer;
+        Node::new("filter_map", Kind::Filter)
+    }
+
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
         #[allow(deprecated)]
         self.parse_inner(d, s)
@@ -888,6 +998,11 @@ impl<I: Clone, O, E: Error<I>> Parser<I, O> for Todo<I, O, E> {
         )
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        let _ = describer;
+        Node::new("todo", Kind::Opaque).with_location(*self.0)
+    }
+
     fn parse_inner_verbose(
         &self,
         d: &mut Verbose,
@@ -908,12 +1023,16 @@ impl<I: Clone, O, E: Error<I>> Parser<I, O> for Todo<I, O, E> {
 
 /// See [`choice`].
 #[must_use]
-pub struct Choice<T, E>(pub(crate) T, pub(crate) PhantomData<E>);
+pub struct Choice<T, E>(
+    pub(crate) T,
+    pub(crate) PhantomData<E>,
+    pub(crate) Location<'static>,
+);
 
 impl<T: Copy, E> Copy for Choice<T, E> {}
 impl<T: Clone, E> Clone for Choice<T, E> {
     fn clone(&self) -> Self {
-        Self(self.0.clone(), PhantomData)
+        Self(self.0.clone(), PhantomData, self.2)
     }
 }
 
@@ -927,7 +1046,7 @@ impl<I: Clone, O, E: Error<I>, A: Parser<I, O, Error = E>, const N: usize> Parse
         debugger: &mut D,
         stream: &mut StreamOf<I, Self::Error>,
     ) -> PResult<I, O, Self::Error> {
-        let Choice(parsers, _) = self;
+        let Choice(parsers, _, _) = self;
         let mut alt = None;
 
         for parser in parsers {
@@ -945,6 +1064,14 @@ impl<I: Clone, O, E: Error<I>, A: Parser<I, O, Error = E>, const N: usize> Parse
         (Vec::new(), Err(alt.unwrap()))
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        Node::new(
+            "choice",
+            Kind::Choice(self.0.iter().map(|p| p.describe(describer)).collect()),
+        )
+        .with_location(self.2)
+    }
+
     fn parse_inner_verbose(
         &self,
         d: &mut Verbose,
@@ -972,7 +1099,7 @@ impl<I: Clone, O, E: Error<I>, A: Parser<I, O, Error = E>> Parser<I, O> for Choi
         debugger: &mut D,
         stream: &mut StreamOf<I, Self::Error>,
     ) -> PResult<I, O, Self::Error> {
-        let Choice(parsers, _) = self;
+        let Choice(parsers, _, _) = self;
         let mut alt = None;
 
         for parser in parsers {
@@ -990,6 +1117,14 @@ impl<I: Clone, O, E: Error<I>, A: Parser<I, O, Error = E>> Parser<I, O> for Choi
         (Vec::new(), Err(alt.unwrap()))
     }
 
+    fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+        Node::new(
+            "choice",
+            Kind::Choice(self.0.iter().map(|p| p.describe(describer)).collect()),
+        )
+        .with_location(self.2)
+    }
+
     fn parse_inner_verbose(
         &self,
         d: &mut Verbose,
@@ -1025,7 +1160,7 @@ macro_rules! impl_for_tuple {
                 debugger: &mut D,
                 stream: &mut StreamOf<I, Self::Error>,
             ) -> PResult<I, O, Self::Error> {
-                let Choice(($($X,)*), _) = self;
+                let Choice(($($X,)*), _, _) = self;
                 let mut alt = None;
                 $(
                     match stream.try_parse(|stream| {
@@ -1041,6 +1176,12 @@ macro_rules! impl_for_tuple {
                 (Vec::new(), Err(alt.unwrap()))
             }
 
+            fn describe(&self, describer: &mut Describer<I, Self::Error>) -> Node<I> {
+                let Choice(($($X,)*), _, location) = self;
+                Node::new("choice", Kind::Choice(vec![$($X.describe(describer)),*]))
+                    .with_location(*location)
+            }
+
             fn parse_inner_verbose(
                 &self,
                 d: &mut Verbose,
@@ -1137,6 +1278,7 @@ impl_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_ I_ J_ K_ L_ M_ N_ O_ P_ Q_ S_ T_ U_ V_ W
 ///     Ok(vec![Def, Fn, While, If, For]),
 /// );
 /// ```
+#[track_caller]
 pub fn choice<T, E>(parsers: T) -> Choice<T, E> {
-    Choice(parsers, PhantomData)
+    Choice(parsers, PhantomData, *Location::caller())
 }
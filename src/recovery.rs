@@ -15,6 +15,155 @@ pub trait Strategy<I: Clone, O, E: Error<I>> {
         debugger: &mut D,
         stream: &mut StreamOf<I, P::Error>,
     ) -> PResult<I, O, P::Error>;
+
+    /// The name of this strategy, as reported by [`RecoveryEvent::strategy`].
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+}
+
+/// A condition that determines where skip-based recovery strategies, such as [`skip_until`], stop skipping inputs.
+///
+/// This trait is implemented for arrays of inputs (which stop on any of the inputs), [`UntilIf`] (which stops on any
+/// input that satisfies a predicate) and [`UntilParser`] (which stops wherever a parser succeeds).
+pub trait Until<I: Clone, E: Error<I>> {
+    /// Check whether the stream is at a synchronisation point, consuming it if so.
+    ///
+    /// Returns `None` if the end of the input has been reached. The position of the stream is unspecified when this
+    /// returns `Some(false)`.
+    fn check<D: Debugger>(&self, debugger: &mut D, stream: &mut StreamOf<I, E>) -> Option<bool>;
+
+    /// The inputs that were expected when the end of the input was reached without finding a synchronisation point.
+    fn expected(&self) -> Vec<I> {
+        Vec::new()
+    }
+}
+
+impl<I: Clone + PartialEq, E: Error<I>, const N: usize> Until<I, E> for [I; N] {
+    fn check<D: Debugger>(&self, _debugger: &mut D, stream: &mut StreamOf<I, E>) -> Option<bool> {
+        stream.next().2.map(|tok| self.contains(&tok))
+    }
+
+    fn expected(&self) -> Vec<I> {
+        self.to_vec()
+    }
+}
+
+/// See [`skip_until_if`] and [`skip_then_retry_until_if`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct UntilIf<F>(pub(crate) F);
+
+impl<I: Clone, E: Error<I>, F: Fn(&I) -> bool> Until<I, E> for UntilIf<F> {
+    fn check<D: Debugger>(&self, _debugger: &mut D, stream: &mut StreamOf<I, E>) -> Option<bool> {
+        stream.next().2.map(|tok| (self.0)(&tok))
+    }
+}
+
+/// See [`skip_until_parser`] and [`skip_then_retry_until_parser`].
+#[must_use]
+pub struct UntilParser<P, O>(pub(crate) P, pub(crate) PhantomData<O>);
+
+impl<P: Copy, O> Copy for UntilParser<P, O> {}
+impl<P: Clone, O> Clone for UntilParser<P, O> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<I: Clone, O, E: Error<I>, P: Parser<I, O, Error = E>> Until<I, E> for UntilParser<P, O> {
+    fn check<D: Debugger>(&self, debugger: &mut D, stream: &mut StreamOf<I, E>) -> Option<bool> {
+        if stream.attempt(|stream| (false, stream.next().2.is_none())) {
+            None
+        } else {
+            #[allow(deprecated)]
+            let res = debugger.invoke(&self.0, stream).1;
+            Some(res.is_ok())
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn skip_then_retry_until_inner<
+    I: Clone,
+    O,
+    E: Error<I>,
+    U: Until<I, E>,
+    D: Debugger,
+    P: Parser<I, O, Error = E>,
+>(
+    until: &U,
+    consume_end: bool,
+    skip_start: bool,
+    a_errors: Vec<Located<I, E>>,
+    a_err: Located<I, E>,
+    parser: P,
+    debugger: &mut D,
+    stream: &mut StreamOf<I, E>,
+) -> PResult<I, O, E> {
+    let _ = stream.next();
+    if skip_start {
+        let _ = stream.next();
+    }
+    loop {
+        let at_sync = stream.attempt(|stream| match until.check(debugger, stream) {
+            Some(true) => (consume_end, true),
+            Some(false) => (false, false),
+            None => (false, true),
+        });
+        if at_sync {
+            break (a_errors, Err(a_err));
+        }
+        #[allow(deprecated)]
+        let (mut errors, res) = debugger.invoke(&parser, stream);
+        if let Ok(out) = res {
+            errors.push(a_err);
+            break (errors, Ok(out));
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn skip_until_inner<I: Clone, O, E: Error<I>, U: Until<I, E>, F: Fn(E::Span) -> O, D: Debugger>(
+    until: &U,
+    fallback: &F,
+    consume_end: bool,
+    skip_start: bool,
+    mut a_errors: Vec<Located<I, E>>,
+    a_err: Located<I, E>,
+    debugger: &mut D,
+    stream: &mut StreamOf<I, E>,
+) -> PResult<I, O, E> {
+    let pre_state = stream.save();
+    if skip_start {
+        let _ = stream.next();
+    }
+    a_errors.push(a_err);
+    loop {
+        match stream.attempt(|stream| match until.check(debugger, stream) {
+            Some(true) => (consume_end, Some(true)),
+            Some(false) => (false, Some(false)),
+            None => (false, None),
+        }) {
+            Some(true) => break (a_errors, Ok((fallback(stream.span_since(pre_state)), None))),
+            Some(false) => {
+                let _ = stream.next();
+            }
+            None if stream.save() > pre_state => {
+                break (a_errors, Ok((fallback(stream.span_since(pre_state)), None)))
+            }
+            None => {
+                let (at, span, _) = stream.next();
+                break (
+                    a_errors,
+                    Err(Located::at(
+                        at,
+                        E::expected_input_found(span, until.expected().into_iter().map(Some), None),
+                    )),
+                );
+            }
+        }
+    }
 }
 
 /// See [`skip_then_retry_until`].
@@ -55,28 +204,13 @@ impl<I: Clone + PartialEq, O, E: Error<I>, const N: usize> Strategy<I, O, E>
         debugger: &mut D,
         stream: &mut StreamOf<I, P::Error>,
     ) -> PResult<I, O, P::Error> {
-        let _ = stream.next();
-        if self.2 {
-            let _ = stream.next();
-        }
-        loop {
-            #[allow(clippy::blocks_in_if_conditions)]
-            if !stream.attempt(
-                |stream| match stream.next().2.map(|tok| self.0.contains(&tok)) {
-                    Some(true) => (self.1, false),
-                    Some(false) => (false, true),
-                    None => (false, false),
-                },
-            ) {
-                break (a_errors, Err(a_err));
-            }
-            #[allow(deprecated)]
-            let (mut errors, res) = debugger.invoke(&parser, stream);
-            if let Ok(out) = res {
-                errors.push(a_err);
-                break (errors, Ok(out));
-            }
-        }
+        skip_then_retry_until_inner(
+            &self.0, self.1, self.2, a_errors, a_err, parser, debugger, stream,
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "skip_then_retry_until"
     }
 }
 
@@ -91,6 +225,78 @@ pub fn skip_then_retry_until<I, const N: usize>(until: [I; N]) -> SkipThenRetryU
     SkipThenRetryUntil(until, false, false)
 }
 
+/// See [`skip_then_retry_until_if`] and [`skip_then_retry_until_parser`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct SkipThenRetryUntilWith<U>(pub(crate) U, pub(crate) bool, pub(crate) bool);
+
+impl<U> SkipThenRetryUntilWith<U> {
+    /// Alters this recovery strategy so that the first token will always be skipped.
+    ///
+    /// This is useful when the input being searched for also appears at the beginning of the pattern that failed to
+    /// parse.
+    pub fn skip_start(self) -> Self {
+        Self(self.0, self.1, true)
+    }
+
+    /// Alters this recovery strategy so that the synchronisation point will be consumed during recovery.
+    ///
+    /// This is useful when the input being searched for is a delimiter of a prior pattern rather than the start of a
+    /// new pattern and hence is no longer important once recovery has occurred.
+    pub fn consume_end(self) -> Self {
+        Self(self.0, true, self.2)
+    }
+}
+
+impl<I: Clone, O, E: Error<I>, U: Until<I, E>> Strategy<I, O, E> for SkipThenRetryUntilWith<U> {
+    fn recover<D: Debugger, P: Parser<I, O, Error = E>>(
+        &self,
+        a_errors: Vec<Located<I, P::Error>>,
+        a_err: Located<I, P::Error>,
+        parser: P,
+        debugger: &mut D,
+        stream: &mut StreamOf<I, P::Error>,
+    ) -> PResult<I, O, P::Error> {
+        skip_then_retry_until_inner(
+            &self.0, self.1, self.2, a_errors, a_err, parser, debugger, stream,
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "skip_then_retry_until"
+    }
+}
+
+/// A recovery mode that simply skips to the next input on parser failure and tries again, until reaching an input
+/// that satisfies the given predicate.
+///
+/// This is useful when synchronising on a class of inputs, such as any keyword.
+///
+/// Also see [`SkipThenRetryUntilWith::consume_end`].
+///
+/// This strategy is very 'stupid' and can result in very poor error generation in some languages. Place this strategy
+/// after others as a last resort, and be careful about over-using it.
+pub fn skip_then_retry_until_if<I, F: Fn(&I) -> bool>(
+    until: F,
+) -> SkipThenRetryUntilWith<UntilIf<F>> {
+    SkipThenRetryUntilWith(UntilIf(until), false, false)
+}
+
+/// A recovery mode that simply skips to the next input on parser failure and tries again, until reaching a point in
+/// the input at which the given parser succeeds.
+///
+/// This is useful when synchronisation points span several inputs or depend on context.
+///
+/// Also see [`SkipThenRetryUntilWith::consume_end`].
+///
+/// This strategy is very 'stupid' and can result in very poor error generation in some languages. Place this strategy
+/// after others as a last resort, and be careful about over-using it.
+pub fn skip_then_retry_until_parser<I: Clone, O, P: Parser<I, O>>(
+    until: P,
+) -> SkipThenRetryUntilWith<UntilParser<P, O>> {
+    SkipThenRetryUntilWith(UntilParser(until, PhantomData), false, false)
+}
+
 /// See [`skip_until`].
 #[must_use]
 #[derive(Copy, Clone)]
@@ -124,42 +330,19 @@ impl<I: Clone + PartialEq, O, F: Fn(E::Span) -> O, E: Error<I>, const N: usize>
 {
     fn recover<D: Debugger, P: Parser<I, O, Error = E>>(
         &self,
-        mut a_errors: Vec<Located<I, P::Error>>,
+        a_errors: Vec<Located<I, P::Error>>,
         a_err: Located<I, P::Error>,
         _parser: P,
-        _debugger: &mut D,
+        debugger: &mut D,
         stream: &mut StreamOf<I, P::Error>,
     ) -> PResult<I, O, P::Error> {
-        let pre_state = stream.save();
-        if self.3 {
-            let _ = stream.next();
-        }
-        a_errors.push(a_err);
-        loop {
-            match stream.attempt(|stream| {
-                let (at, span, tok) = stream.next();
-                match tok.map(|tok| self.0.contains(&tok)) {
-                    Some(true) => (self.2, Ok(true)),
-                    Some(false) => (true, Ok(false)),
-                    None => (true, Err((at, span))),
-                }
-            }) {
-                Ok(true) => break (a_errors, Ok(((self.1)(stream.span_since(pre_state)), None))),
-                Ok(false) => {}
-                Err(_) if stream.save() > pre_state => {
-                    break (a_errors, Ok(((self.1)(stream.span_since(pre_state)), None)))
-                }
-                Err((at, span)) => {
-                    break (
-                        a_errors,
-                        Err(Located::at(
-                            at,
-                            E::expected_input_found(span, self.0.iter().cloned().map(Some), None),
-                        )),
-                    )
-                }
-            }
-        }
+        skip_until_inner(
+            &self.0, &self.1, self.2, self.3, a_errors, a_err, debugger, stream,
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "skip_until"
     }
 }
 
@@ -173,6 +356,125 @@ pub fn skip_until<I, F, const N: usize>(until: [I; N], fallback: F) -> SkipUntil
     SkipUntil(until, fallback, false, false)
 }
 
+/// See [`skip_until_if`] and [`skip_until_parser`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct SkipUntilWith<U, F>(pub(crate) U, pub(crate) F, pub(crate) bool, pub(crate) bool);
+
+impl<U, F> SkipUntilWith<U, F> {
+    /// Alters this recovery strategy so that the first token will always be skipped.
+    ///
+    /// This is useful when the input being searched for also appears at the beginning of the pattern that failed to
+    /// parse.
+    pub fn skip_start(self) -> Self {
+        Self(self.0, self.1, self.2, true)
+    }
+
+    /// Alters this recovery strategy so that the synchronisation point will be consumed during recovery.
+    ///
+    /// This is useful when the input being searched for is a delimiter of a prior pattern rather than the start of a
+    /// new pattern and hence is no longer important once recovery has occurred.
+    pub fn consume_end(self) -> Self {
+        Self(self.0, self.1, true, self.3)
+    }
+}
+
+impl<I: Clone, O, U: Until<I, E>, F: Fn(E::Span) -> O, E: Error<I>> Strategy<I, O, E>
+    for SkipUntilWith<U, F>
+{
+    fn recover<D: Debugger, P: Parser<I, O, Error = E>>(
+        &self,
+        a_errors: Vec<Located<I, P::Error>>,
+        a_err: Located<I, P::Error>,
+        _parser: P,
+        debugger: &mut D,
+        stream: &mut StreamOf<I, P::Error>,
+    ) -> PResult<I, O, P::Error> {
+        skip_until_inner(
+            &self.0, &self.1, self.2, self.3, a_errors, a_err, debugger, stream,
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "skip_until"
+    }
+}
+
+/// A recovery mode that skips input until an input that satisfies the given predicate is found.
+///
+/// This is useful when synchronising on a class of inputs, such as any keyword.
+///
+/// Also see [`SkipUntilWith::consume_end`].
+///
+/// This strategy is very 'stupid' and can result in very poor error generation in some languages. Place this strategy
+/// after others as a last resort, and be careful about over-using it.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// enum Token {
+///     Ident(String),
+///     Num(u64),
+///     Semicolon,
+/// }
+///
+/// let stmt = select! { Token::Ident(x) => x }
+///     .then_ignore(just(Token::Semicolon))
+///     // Skip to the next identifier
+///     .recover_with(skip_until_if(|t| matches!(t, Token::Ident(_)), |_| "error".to_string()));
+///
+/// let tokens = vec![
+///     Token::Ident("a".to_string()),
+///     Token::Semicolon,
+///     Token::Num(42),
+///     Token::Semicolon,
+///     Token::Ident("b".to_string()),
+///     Token::Semicolon,
+/// ];
+///
+/// let (stmts, errors) = stmt.repeated().then_ignore(end::<Simple<Token>>()).parse_recovery(tokens);
+/// assert_eq!(stmts, Some(vec!["a".to_string(), "error".to_string(), "b".to_string()]));
+/// assert_eq!(errors.len(), 1);
+/// ```
+pub fn skip_until_if<I, F: Fn(&I) -> bool, G>(
+    until: F,
+    fallback: G,
+) -> SkipUntilWith<UntilIf<F>, G> {
+    SkipUntilWith(UntilIf(until), fallback, false, false)
+}
+
+/// A recovery mode that skips input until a point in the input at which the given parser succeeds.
+///
+/// This is useful when synchronisation points span several inputs or depend on context.
+///
+/// Also see [`SkipUntilWith::consume_end`], which will consume the input matched by the parser.
+///
+/// This strategy is very 'stupid' and can result in very poor error generation in some languages. Place this strategy
+/// after others as a last resort, and be careful about over-using it.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let stmt = text::ident::<_, Simple<char>>()
+///     .then_ignore(just(';'))
+///     // Skip past the end of the current line
+///     .recover_with(skip_until_parser(text::newline(), |_| "error".to_string()).consume_end())
+///     .padded();
+///
+/// let (stmts, errors) = stmt.repeated().then_ignore(end()).parse_recovery("a;\n4 2;\nb;");
+/// assert_eq!(stmts, Some(vec!["a".to_string(), "error".to_string(), "b".to_string()]));
+/// assert_eq!(errors.len(), 1);
+/// ```
+pub fn skip_until_parser<I: Clone, O, P: Parser<I, O>, F>(
+    until: P,
+    fallback: F,
+) -> SkipUntilWith<UntilParser<P, O>, F> {
+    SkipUntilWith(UntilParser(until, PhantomData), fallback, false, false)
+}
+
 /// See [`nested_delimiters`].
 #[must_use]
 #[derive(Copy, Clone)]
@@ -291,6 +593,10 @@ impl<I: Clone + PartialEq, O, F: Fn(E::Span) -> O, E: Error<I>, const N: usize>
             (a_errors, Err(a_err))
         }
     }
+
+    fn name(&self) -> &'static str {
+        "nested_delimiters"
+    }
 }
 
 /// A recovery strategy that searches for a start and end delimiter, respecting nesting.
@@ -299,6 +605,9 @@ impl<I: Clone + PartialEq, O, F: Fn(E::Span) -> O, E: Error<I>, const N: usize>
 /// example, you might want to also specify `[('[', ']'), ('{', '}')]` when recovering a parenthesised expression as
 /// this can aid in detecting delimiter mismatches.
 ///
+/// Note that the additional pairs are only counted, so mismatched delimiters such as `( [ ) ]` are not detected. Use
+/// [`nested_delimiters_any`] for stricter checking.
+///
 /// A function that generates a fallback output on recovery is also required.
 pub fn nested_delimiters<I: PartialEq, F, const N: usize>(
     start: I,
@@ -313,10 +622,142 @@ pub fn nested_delimiters<I: PartialEq, F, const N: usize>(
     NestedDelimiters(start, end, others, fallback)
 }
 
+/// See [`nested_delimiters_any`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct NestedDelimitersAny<I, F, const N: usize>(pub(crate) [(I, I); N], pub(crate) F);
+
+impl<I: Clone + PartialEq, O, F: Fn(E::Span) -> O, E: Error<I>, const N: usize> Strategy<I, O, E>
+    for NestedDelimitersAny<I, F, N>
+{
+    fn recover<D: Debugger, P: Parser<I, O, Error = E>>(
+        &self,
+        mut a_errors: Vec<Located<I, P::Error>>,
+        a_err: Located<I, P::Error>,
+        _parser: P,
+        _debugger: &mut D,
+        stream: &mut StreamOf<I, P::Error>,
+    ) -> PResult<I, O, P::Error> {
+        // Each open delimiter, along with its span and the index of its pair
+        let mut open: Vec<(I, E::Span, usize)> = Vec::new();
+        let mut error = None;
+        let pre_state = stream.save();
+        let recovered = loop {
+            match stream.next() {
+                (_, span, Some(t)) if self.0.iter().any(|(start, _)| t == *start) => {
+                    let pair = self.0.iter().position(|(start, _)| t == *start).unwrap();
+                    open.push((t, span, pair));
+                }
+                // A non-delimiter input before anything else is not a valid recovery pattern
+                (_, _, _) if open.is_empty() => break false,
+                (at, span, Some(t)) if self.0.iter().any(|(_, end)| t == *end) => {
+                    let (start, start_span, pair) = open.last().unwrap();
+                    if t != self.0[*pair].1 {
+                        error.get_or_insert_with(|| {
+                            Located::at(
+                                at,
+                                P::Error::mismatched_delimiter(
+                                    start_span.clone(),
+                                    start.clone(),
+                                    span,
+                                    self.0[*pair].1.clone(),
+                                    t.clone(),
+                                ),
+                            )
+                        });
+                    }
+                    // Close the innermost delimiter that this input closes, treating any delimiters opened since as
+                    // unclosed. Closing delimiters that close nothing are skipped.
+                    if let Some(idx) = open.iter().rposition(|(_, _, pair)| t == self.0[*pair].1) {
+                        open.truncate(idx);
+                        if open.is_empty() {
+                            break true;
+                        }
+                    }
+                }
+                (_, _, Some(_)) => {}
+                (at, span, None) => {
+                    let (start, start_span, pair) = open.pop().unwrap();
+                    error.get_or_insert_with(|| {
+                        Located::at(
+                            at,
+                            P::Error::unclosed_delimiter(
+                                start_span,
+                                start,
+                                span,
+                                self.0[pair].1.clone(),
+                                None,
+                            ),
+                        )
+                    });
+                    break false;
+                }
+            }
+        };
+
+        if let Some(e) = error {
+            a_errors.push(e);
+        }
+
+        if recovered {
+            if !matches!(a_errors.last(), Some(e) if e.at <= a_err.at) {
+                a_errors.push(a_err);
+            }
+            (a_errors, Ok(((self.1)(stream.span_since(pre_state)), None)))
+        } else {
+            (a_errors, Err(a_err))
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "nested_delimiters_any"
+    }
+}
+
+/// A recovery strategy that searches for a balanced sequence of delimiters starting with any of the given pairs.
+///
+/// Unlike [`nested_delimiters`], every pair is tracked on a single stack so that delimiters must be closed in the
+/// order that they were opened. When a closing delimiter does not match the innermost open delimiter, an
+/// [`Error::mismatched_delimiter`] error is generated that points at both the closing delimiter and the open
+/// delimiter it fails to close. Recovery then continues by closing the innermost delimiter that the closing delimiter
+/// does match, if any. If the end of the input is reached first, an [`Error::unclosed_delimiter`] error is generated
+/// and recovery fails.
+///
+/// A function that generates a fallback output on recovery is also required.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, error::SimpleReason};
+/// let expr = just::<_, _, Simple<char>>('x')
+///     .delimited_by(just('('), just(')'))
+///     .recover_with(nested_delimiters_any([('(', ')'), ('[', ']')], |_| '?'));
+///
+/// let (out, errors) = expr.then_ignore(end()).parse_recovery("([x)]");
+/// assert_eq!(out, None);
+/// assert_eq!(errors[0].span(), 3..4);
+/// assert_eq!(errors[0].reason(), &SimpleReason::Mismatched { span: 1..2, delimiter: '[' });
+///
+/// // Recovery starts from whichever delimiter is found first
+/// let (out, errors) = expr.then_ignore(end()).parse_recovery("[(x]");
+/// assert_eq!(out, Some('?'));
+/// assert_eq!(errors[0].reason(), &SimpleReason::Mismatched { span: 1..2, delimiter: '(' });
+/// ```
+pub fn nested_delimiters_any<I: PartialEq, F, const N: usize>(
+    pairs: [(I, I); N],
+    fallback: F,
+) -> NestedDelimitersAny<I, F, N> {
+    assert!(
+        pairs.iter().all(|(start, end)| start != end),
+        "Start and end delimiters cannot be the same when using `NestedDelimitersAny`"
+    );
+    NestedDelimitersAny(pairs, fallback)
+}
+
 /// A parser that includes a fallback recovery strategy should parsing result in an error.
 #[must_use]
 #[derive(Copy, Clone)]
-pub struct Recovery<A, S>(pub(crate) A, pub(crate) S);
+pub struct Recovery<A, S>(pub(crate) A, pub(crate) S, pub(crate) Location<'static>);
 
 impl<I: Clone, O, A: Parser<I, O, Error = E>, S: Strategy<I, O, E>, E: Error<I>> Parser<I, O>
     for Recovery<A, S>
@@ -328,15 +769,27 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, S: Strategy<I, O, E>, E: Error<I>>
         debugger: &mut D,
         stream: &mut StreamOf<I, E>,
     ) -> PResult<I, O, E> {
+        let pre_state = stream.save();
         match stream.try_parse(|stream| {
             #[allow(deprecated)]
             debugger.invoke(&self.0, stream)
         }) {
             (a_errors, Ok(a_out)) => (a_errors, Ok(a_out)),
-            (a_errors, Err(a_err)) => self.1.recover(a_errors, a_err, &self.0, debugger, stream),
+            (a_errors, Err(a_err)) if !stream.can_recover() => (a_errors, Err(a_err)),
+            (a_errors, Err(a_err)) => {
+                let (mut errors, res) = self.1.recover(a_errors, a_err, &self.0, debugger, stream);
+                if res.is_ok() {
+                    stream.recovered(pre_state, &mut errors, self.1.name(), self.2);
+                }
+                (errors, res)
+            }
         }
     }
 
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        Node::wrap("recover_with", self.0.describe(describer)).with_location(self.2)
+    }
+
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
         #[allow(deprecated)]
         self.parse_inner(d, s)
@@ -347,6 +800,143 @@ impl<I: Clone, O, A: Parser<I, O, Error = E>, S: Strategy<I, O, E>, E: Error<I>>
     }
 }
 
+/// A trait implemented by item-level recovery modes of [`Parser::repeated`] and [`Parser::separated_by`].
+///
+/// `()` is the default mode, which never recovers.
+pub trait ItemRecovery<I: Clone, O, E: Error<I>> {
+    /// Recover from an item that failed to parse after starting at the offset `start`, producing `errors`.
+    ///
+    /// `sync` is called to check whether the stream is at a valid synchronisation point (such as a separator) without
+    /// consuming any input. If recovery succeeds, `Some` is returned, containing an optional placeholder output.
+    fn recover_item(
+        &self,
+        start: usize,
+        errors: &mut [Located<I, E>],
+        stream: &mut StreamOf<I, E>,
+        sync: &mut dyn FnMut(&mut StreamOf<I, E>) -> bool,
+    ) -> Option<Option<O>>;
+}
+
+impl<I: Clone, O, E: Error<I>> ItemRecovery<I, O, E> for () {
+    fn recover_item(
+        &self,
+        _start: usize,
+        _errors: &mut [Located<I, E>],
+        _stream: &mut StreamOf<I, E>,
+        _sync: &mut dyn FnMut(&mut StreamOf<I, E>) -> bool,
+    ) -> Option<Option<O>> {
+        None
+    }
+}
+
+/// See [`SeparatedBy::recover_items`] and [`Repeated::recover_items`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct RecoverItems<I, F, const N: usize>(
+    pub(crate) [I; N],
+    pub(crate) F,
+    pub(crate) Location<'static>,
+);
+
+fn skip_item<I: Clone + PartialEq, E: Error<I>>(
+    until: &[I],
+    start: usize,
+    stream: &mut StreamOf<I, E>,
+    sync: &mut dyn FnMut(&mut StreamOf<I, E>) -> bool,
+) -> bool {
+    stream.revert(start);
+    if !stream.can_recover() {
+        return false;
+    }
+    loop {
+        if sync(stream) {
+            break true;
+        }
+        let skipped = stream.attempt(|stream| match stream.next().2 {
+            Some(tok) if !until.contains(&tok) => (true, true),
+            _ => (false, false),
+        });
+        if !skipped {
+            // Only recover if we actually skipped something, otherwise the terminator or end of input is left for the
+            // parser that follows.
+            break stream.offset() > start;
+        }
+    }
+}
+
+impl<I: Clone + PartialEq, O, E: Error<I>, const N: usize> ItemRecovery<I, O, E>
+    for RecoverItems<I, (), N>
+{
+    fn recover_item(
+        &self,
+        start: usize,
+        errors: &mut [Located<I, E>],
+        stream: &mut StreamOf<I, E>,
+        sync: &mut dyn FnMut(&mut StreamOf<I, E>) -> bool,
+    ) -> Option<Option<O>> {
+        if skip_item::<I, E>(&self.0, start, stream, sync) {
+            stream.recovered(start, errors, "recover_items", self.2);
+            Some(None)
+        } else {
+            stream.revert(start);
+            None
+        }
+    }
+}
+
+impl<I: Clone + PartialEq, O, F: Fn(E::Span) -> O, E: Error<I>, const N: usize>
+    ItemRecovery<I, O, E> for RecoverItems<I, F, N>
+{
+    fn recover_item(
+        &self,
+        start: usize,
+        errors: &mut [Located<I, E>],
+        stream: &mut StreamOf<I, E>,
+        sync: &mut dyn FnMut(&mut StreamOf<I, E>) -> bool,
+    ) -> Option<Option<O>> {
+        if skip_item::<I, E>(&self.0, start, stream, sync) {
+            stream.recovered(start, errors, "recover_items", self.2);
+            Some(Some((self.1)(stream.span_since(start))))
+        } else {
+            stream.revert(start);
+            None
+        }
+    }
+}
+
+/// Skip inputs until the delimiter that closes an already-consumed `start` is found, respecting the nesting of both
+/// it and `others`.
+///
+/// Closing delimiters that don't match the innermost open delimiter are skipped. Returns `true` if the closing
+/// delimiter was found (in which case it is consumed), or `false` if the end of the input was reached first.
+pub(crate) fn skip_to_close<I: Clone + PartialEq, S: Span>(
+    stream: &mut Stream<I, S>,
+    start: &I,
+    end: &I,
+    others: &[(I, I)],
+) -> bool {
+    let mut closers = vec![end.clone()];
+    loop {
+        match stream.next().2 {
+            Some(t) if &t == end || others.iter().any(|(_, end)| &t == end) => {
+                if closers.last() == Some(&t) {
+                    closers.pop();
+                }
+                if closers.is_empty() {
+                    break true;
+                }
+            }
+            Some(t) if &t == start => closers.push(end.clone()),
+            Some(t) => {
+                if let Some((_, end)) = others.iter().find(|(start, _)| &t == start) {
+                    closers.push(end.clone());
+                }
+            }
+            None => break false,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::error::Cheap;
@@ -373,4 +963,212 @@ mod tests {
             assert_eq!(errors.len(), 1)
         }
     }
+
+    #[test]
+    fn separated_by_recover_items() {
+        let parser = just::<_, _, Cheap<_>>('a')
+            .separated_by(just(','))
+            .recover_items_with([';'], |_| 'x')
+            .then_ignore(just(';'));
+        {
+            let (result, errors) = parser.parse_recovery("a,b,a;");
+            assert_eq!(result, Some(vec!['a', 'x', 'a']));
+            assert_eq!(errors.len(), 1)
+        }
+        {
+            let (result, errors) = parser.parse_recovery("a,,bb;");
+            assert_eq!(result, Some(vec!['a', 'x', 'x']));
+            assert_eq!(errors.len(), 2)
+        }
+        {
+            let (result, errors) = parser.parse_recovery("a,a;");
+            assert_eq!(result, Some(vec!['a', 'a']));
+            assert_eq!(errors.len(), 0)
+        }
+    }
+
+    #[test]
+    fn suppress_cascading_errors() {
+        let expr = just::<_, _, Cheap<_>>('x')
+            .delimited_by(just('('), just(')'))
+            .recover_with(nested_delimiters('(', ')', [], |_| '?'));
+        let stmt = expr
+            .then_ignore(just(';'))
+            .recover_with(skip_until([';'], |_| '!').consume_end());
+        let parser = stmt.repeated().then_ignore(end());
+
+        let output = parser.parse_recovery_with("(y)+;(x);(z);", crate::ParseConfig::new());
+        assert_eq!(output.output(), Some(&vec!['!', 'x', '?']));
+        assert_eq!(output.errors().len(), 3);
+        assert_eq!(output.clone(), output);
+
+        let output = parser.parse_recovery_with(
+            "(y)+;(x);(z);",
+            crate::ParseConfig::new().suppress_cascading(0),
+        );
+        assert_eq!(
+            output.errors().iter().map(|e| e.span()).collect::<Vec<_>>(),
+            vec![1..2, 10..11],
+        );
+
+        let output =
+            parser.parse_recovery_with("(y)+;(x);(z);", crate::ParseConfig::new().max_errors(1));
+        assert_eq!(output.output(), None);
+        assert_eq!(output.errors().len(), 1);
+    }
+
+    #[test]
+    fn max_errors_ignores_backtracked_recoveries() {
+        let delimited = just::<_, _, Cheap<_>>('x')
+            .delimited_by(just('('), just(')'))
+            .recover_with(nested_delimiters('(', ')', [], |_| '?'));
+        let stmt = delimited
+            .then_ignore(just('!'))
+            .or(just('(').ignore_then(any()).then_ignore(just(')')))
+            .then_ignore(just(';'))
+            .recover_with(skip_until([';'], |_| '!').consume_end());
+        let parser = stmt.repeated().then_ignore(end());
+
+        // The recovery within the first alternative of the first statement is discarded along with the alternative
+        let output = parser.parse_recovery_with("(y);q;", crate::ParseConfig::new().max_errors(1));
+        assert_eq!(output.output(), Some(&vec!['y', '!']));
+        assert_eq!(output.errors().len(), 1);
+    }
+
+    #[test]
+    fn merge_errors() {
+        let parser = any::<_, Simple<char>>().validate(|c, span, emit| {
+            emit(Simple::custom(span.clone(), "first"));
+            emit(Simple::custom(span.clone(), "second"));
+            emit(Simple::custom(span, "first"));
+            c
+        });
+
+        let output = parser.parse_recovery_with("a", crate::ParseConfig::new());
+        assert_eq!(output.errors().len(), 3);
+
+        // Only the repeated error is removed, distinct errors at the same position both survive
+        let output = parser.parse_recovery_with("a", crate::ParseConfig::new().merge_errors());
+        assert_eq!(
+            output.errors(),
+            &[
+                Simple::custom(0..1, "first"),
+                Simple::custom(0..1, "second")
+            ],
+        );
+        assert_eq!(output.output(), Some(&'a'));
+    }
+
+    #[test]
+    fn log_recoveries() {
+        let parser = just::<_, _, Cheap<_>>('a')
+            .recover_with(skip_then_retry_until([',']))
+            .separated_by(just(','));
+        let line = line!() - 2;
+
+        let output =
+            parser.parse_recovery_with("a,2a,3a,a", crate::ParseConfig::new().log_recoveries());
+        let recoveries = output.recoveries();
+        assert_eq!(recoveries.len(), 2);
+        assert_eq!(recoveries[0].strategy(), "skip_then_retry_until");
+        assert_eq!(recoveries[0].location().line(), line);
+        assert_eq!(recoveries[0].location().file(), file!());
+        assert_eq!(recoveries[0].span(), &(2..4));
+        assert_eq!(recoveries[0].skipped(), 2);
+        assert_eq!(recoveries[0].errors(), &[0]);
+        assert_eq!(recoveries[1].span(), &(5..7));
+        assert_eq!(recoveries[1].errors(), &[1]);
+
+        let output = parser.parse_recovery_with("a,2a,3a,a", crate::ParseConfig::new());
+        assert!(output.recoveries().is_empty());
+    }
+
+    #[test]
+    fn recover_with_skip_then_retry_until_if() {
+        let parser = just::<_, _, Cheap<_>>('a')
+            .recover_with(skip_then_retry_until_if(|c: &char| {
+                c.is_ascii_punctuation()
+            }))
+            .separated_by(one_of([',', ';']));
+        {
+            let (result, errors) = parser.parse_recovery("a,a;2a,a");
+            assert_eq!(result, Some(vec!['a', 'a', 'a', 'a']));
+            assert_eq!(errors.len(), 1)
+        }
+        {
+            let (result, errors) = parser.parse_recovery("a,a;23 a,a");
+            assert_eq!(result, Some(vec!['a', 'a', 'a', 'a']));
+            assert_eq!(errors.len(), 1)
+        }
+    }
+
+    #[test]
+    fn recover_with_skip_until_parser() {
+        let parser = just::<_, _, Cheap<_>>('a')
+            .then_ignore(just(';'))
+            .recover_with(skip_until_parser(just("end"), |_| 'x').consume_end())
+            .repeated()
+            .then_ignore(end());
+        {
+            let (result, errors) = parser.parse_recovery("a;b;cenda;");
+            assert_eq!(result, Some(vec!['a', 'x', 'a']));
+            assert_eq!(errors.len(), 1)
+        }
+        {
+            let (result, errors) = parser.parse_recovery("a;b;c");
+            assert_eq!(result, Some(vec!['a', 'x']));
+            assert_eq!(errors.len(), 1)
+        }
+    }
+
+    #[test]
+    fn recover_with_nested_delimiters_any() {
+        let parser = just::<_, _, Simple<_>>('x')
+            .delimited_by(just('('), just(')'))
+            .recover_with(nested_delimiters_any(
+                [('(', ')'), ('[', ']'), ('{', '}')],
+                |_| '?',
+            ))
+            .repeated()
+            .then_ignore(end());
+        {
+            let (result, errors) = parser.parse_recovery("(x)([{}])(x)");
+            assert_eq!(result, Some(vec!['x', '?', 'x']));
+            assert_eq!(errors.len(), 1)
+        }
+        {
+            let (result, errors) = parser.parse_recovery("([)](x)");
+            assert_eq!(result, None);
+            assert_eq!(errors[0].span(), 2..3);
+            assert_eq!(
+                errors[0].reason(),
+                &crate::error::SimpleReason::Mismatched {
+                    span: 1..2,
+                    delimiter: '['
+                },
+            );
+            assert_eq!(errors[0].expected().collect::<Vec<_>>(), vec![&Some(']')]);
+            assert_eq!(errors[0].found(), Some(&')'));
+        }
+        {
+            let (result, errors) = parser.parse_recovery("(x){[x)}(x)");
+            assert_eq!(result, Some(vec!['x', '?', 'x']));
+            assert_eq!(
+                errors[0].reason(),
+                &crate::error::SimpleReason::Mismatched {
+                    span: 4..5,
+                    delimiter: '['
+                },
+            );
+        }
+        {
+            let (result, errors) = parser.parse_recovery("(x)[(x)");
+            assert_eq!(result, None);
+            assert!(errors.iter().any(|e| e.reason()
+                == &crate::error::SimpleReason::Unclosed {
+                    span: 3..4,
+                    delimiter: '['
+                }));
+        }
+    }
 }
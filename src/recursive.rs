@@ -47,7 +47,10 @@ type OnceParser<'a, I, O, E> = OnceCell<Box<dyn Parser<I, O, Error = E> + 'a>>;
 ///
 /// Prefer to use [`recursive()`], which exists as a convenient wrapper around both operations, if possible.
 #[must_use]
-pub struct Recursive<'a, I, O, E: Error<I>>(RecursiveInner<OnceParser<'a, I, O, E>>);
+pub struct Recursive<'a, I, O, E: Error<I>>(
+    RecursiveInner<OnceParser<'a, I, O, E>>,
+    Location<'static>,
+);
 
 impl<'a, I: Clone, O, E: Error<I>> Recursive<'a, I, O, E> {
     fn cell(&self) -> Rc<OnceParser<'a, I, O, E>> {
@@ -97,8 +100,12 @@ impl<'a, I: Clone, O, E: Error<I>> Recursive<'a, I, O, E> {
     ///     Ok(Chain::Link('+', Box::new(Chain::Link('+', Box::new(Chain::End))))),
     /// );
     /// ```
+    #[track_caller]
     pub fn declare() -> Self {
-        Recursive(RecursiveInner::Owned(Rc::new(OnceCell::new())))
+        Recursive(
+            RecursiveInner::Owned(Rc::new(OnceCell::new())),
+            *Location::caller(),
+        )
     }
 
     /// Defines the parser after declaring it, allowing it to be used for parsing.
@@ -111,10 +118,13 @@ impl<'a, I: Clone, O, E: Error<I>> Recursive<'a, I, O, E> {
 
 impl<'a, I: Clone, O, E: Error<I>> Clone for Recursive<'a, I, O, E> {
     fn clone(&self) -> Self {
-        Self(match &self.0 {
-            RecursiveInner::Owned(x) => RecursiveInner::Owned(x.clone()),
-            RecursiveInner::Unowned(x) => RecursiveInner::Unowned(x.clone()),
-        })
+        Self(
+            match &self.0 {
+                RecursiveInner::Owned(x) => RecursiveInner::Owned(x.clone()),
+                RecursiveInner::Unowned(x) => RecursiveInner::Unowned(x.clone()),
+            },
+            self.1,
+        )
     }
 }
 
@@ -137,16 +147,54 @@ impl<'a, I: Clone, O, E: Error<I>> Parser<I, O> for Recursive<'a, I, O, E> {
             f()
         }
 
-        recurse(|| {
+        let cell = self.cell();
+        let key = Rc::as_ptr(&cell) as *const ();
+        let offset = stream.offset();
+
+        // Invocations further up the stack started at the same offset or earlier, so only those at this offset need
+        // to be checked for re-entry
+        let reentered = stream
+            .state
+            .recursion
+            .iter()
+            .rev()
+            .take_while(|(_, at)| *at == offset)
+            .any(|(k, _)| *k == key);
+        if reentered {
+            return (Vec::new(), Err(stream.left_recursion(self.1)));
+        }
+
+        if let Some(max) = stream.state.config.max_depth {
+            if stream.state.recursion.len() >= max {
+                return (
+                    Vec::new(),
+                    Err(stream.abandon(Abandon::Limit(Limit::Depth(max)))),
+                );
+            }
+        }
+
+        stream.state.recursion.push((key, offset));
+        let res = recurse(|| {
             #[allow(deprecated)]
             debugger.invoke(
-                self.cell()
-                    .get()
+                cell.get()
                     .expect("Recursive parser used before being defined")
                     .as_ref(),
                 stream,
             )
-        })
+        });
+        stream.state.recursion.pop();
+        res
+    }
+
+    fn describe(&self, describer: &mut Describer<I, E>) -> Node<I> {
+        let cell = self.cell();
+        let id = describer.rule(Rc::as_ptr(&cell) as *const (), self.1, |describer| {
+            cell.get()
+                .expect("Recursive parser used before being defined")
+                .describe(describer)
+        });
+        Node::new("recursive", Kind::Rule(id))
     }
 
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<I, E>) -> PResult<I, O, E> {
@@ -207,6 +255,7 @@ impl<'a, I: Clone, O, E: Error<I>> Parser<I, O> for Recursive<'a, I, O, E> {
 ///     ]),
 /// ])));
 /// ```
+#[track_caller]
 pub fn recursive<
     'a,
     I: Clone,
@@ -218,9 +267,12 @@ pub fn recursive<
     f: F,
 ) -> Recursive<'a, I, O, E> {
     let mut parser = Recursive::declare();
-    parser.define(f(Recursive(match &parser.0 {
-        RecursiveInner::Owned(x) => RecursiveInner::Unowned(Rc::downgrade(x)),
-        RecursiveInner::Unowned(_) => unreachable!(),
-    })));
+    parser.define(f(Recursive(
+        match &parser.0 {
+            RecursiveInner::Owned(x) => RecursiveInner::Unowned(Rc::downgrade(x)),
+            RecursiveInner::Unowned(_) => unreachable!(),
+        },
+        parser.1,
+    )));
     parser
 }
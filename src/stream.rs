@@ -6,7 +6,15 @@
 //! ways: from strings, iterators, arrays, etc.
 
 use super::*;
-use alloc::vec;
+use crate::incremental::{shift_offset, MemoCheckpoint};
+use alloc::{format, vec};
+use core::{any::Any, sync::atomic::Ordering as AtomicOrdering};
+
+/// The number of parser invocations between checks for cancellation and reports of progress. Must be a power of two.
+const CHECK_INTERVAL: usize = 1024;
+
+/// A hidden input, along with the offset of the input that follows it.
+pub(crate) type Hidden<I, S> = (usize, (I, S));
 
 trait StreamExtend<T>: Iterator<Item = T> {
     /// Extend the vector with input. The actual amount can be more or less than `n`, but must be at least 1 (0 implies
@@ -43,6 +51,13 @@ pub struct Stream<
     pub(crate) eoi: S,
     pub(crate) offset: usize,
     pub(crate) buffer: Vec<(I, S)>,
+    /// Decides which inputs are trivia, hidden from parsers (see [`Stream::from_iter_with_trivia`]).
+    pub(crate) is_trivia: Option<fn(&I) -> bool>,
+    /// Renders inputs as text in debugging output (see [`Stream::describe_tokens`]).
+    pub(crate) describe_input: Option<fn(&I) -> String>,
+    /// The hidden inputs pulled so far, along with the offset of the input that follows each of them.
+    pub(crate) hidden: Vec<Hidden<I, S>>,
+    pub(crate) state: ParseState,
     pub(crate) iter: Iter,
 }
 
@@ -61,10 +76,94 @@ impl<'a, I, S: Span, Iter: Iterator<Item = (I, S)>> Stream<'a, I, S, Iter> {
             eoi,
             offset: 0,
             buffer: Vec::new(),
+            is_trivia: None,
+            describe_input: None,
+            hidden: Vec::new(),
+            state: ParseState::default(),
             iter,
         }
     }
 
+    /// Create a new stream from an iterator of `(Token, Span)` pairs, like [`Stream::from_iter`], but hiding the
+    /// tokens for which `is_trivia` returns `true` (such as comments and newlines emitted by a lexer).
+    ///
+    /// Hidden tokens are invisible to parsers, so grammar rules need not skip them explicitly. They can still be
+    /// retrieved with [`trivia_before`], and they appear in concrete syntax trees (see [`ParseConfig::cst`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, primitive::trivia_before, Stream};
+    /// #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    /// enum Token {
+    ///     Comment(&'static str),
+    ///     Newline,
+    ///     Fn,
+    ///     Ident(&'static str),
+    /// }
+    ///
+    /// let item = trivia_before::<_, Simple<Token>>()
+    ///     .then_ignore(just(Token::Fn))
+    ///     .then(select! { Token::Ident(name) => name })
+    ///     .map(|(trivia, name)| {
+    ///         let docs = trivia
+    ///             .into_iter()
+    ///             .filter_map(|(token, _)| match token {
+    ///                 Token::Comment(c) => Some(c),
+    ///                 _ => None,
+    ///             })
+    ///             .collect::<Vec<_>>();
+    ///         (name, docs)
+    ///     });
+    ///
+    /// let tokens = vec![
+    ///     Token::Comment("/// Does a thing."),
+    ///     Token::Newline,
+    ///     Token::Fn,
+    ///     Token::Ident("a"),
+    ///     Token::Newline,
+    ///     Token::Fn,
+    ///     Token::Newline,
+    ///     Token::Ident("b"),
+    /// ];
+    /// let len = tokens.len();
+    /// let stream = Stream::from_iter_with_trivia(
+    ///     len..len,
+    ///     tokens.into_iter().enumerate().map(|(i, t)| (t, i..i + 1)),
+    ///     |t| matches!(t, Token::Comment(_) | Token::Newline),
+    /// );
+    ///
+    /// assert_eq!(
+    ///     item.repeated().then_ignore(end()).parse(stream),
+    ///     Ok(vec![("a", vec!["/// Does a thing."]), ("b", vec![])]),
+    /// );
+    /// ```
+    pub fn from_iter_with_trivia(eoi: S, iter: Iter, is_trivia: fn(&I) -> bool) -> Self {
+        Self {
+            is_trivia: Some(is_trivia),
+            ..Self::from_iter(eoi, iter)
+        }
+    }
+
+    /// Describe the tokens of the stream using their [`Debug`](fmt::Debug) implementation in the errors within
+    /// debugging output, such as a [`Trace`] or an [`Explanation`] (see [`Error::describe`]).
+    ///
+    /// Streams created from strings already do this. Otherwise, errors are described without mentioning the tokens
+    /// involved.
+    pub fn describe_tokens(self) -> Self
+    where
+        I: fmt::Debug,
+    {
+        fn describe<I: fmt::Debug>(input: &I) -> String {
+            format!("{:?}", input)
+        }
+
+        Self {
+            describe_input: Some(describe::<I>),
+            ..self
+        }
+    }
+
     /// Eagerly evaluate the token stream, returning an iterator over the tokens in it (but without modifying the
     /// stream's state so that it can still be used for parsing).
     ///
@@ -73,11 +172,49 @@ impl<'a, I, S: Span, Iter: Iterator<Item = (I, S)>> Stream<'a, I, S, Iter> {
     where
         (I, S): Clone,
     {
-        self.buffer.extend(&mut self.iter);
+        self.pull_all();
         self.buffer.iter().cloned()
     }
 }
 
+impl<'a, I, S: Span, Iter: Iterator<Item = (I, S)> + ?Sized> Stream<'a, I, S, Iter> {
+    /// Pull inputs from the iterator until the input at the given offset (or the end of the input) is reached, setting
+    /// aside hidden inputs (see [`Stream::from_iter_with_trivia`]).
+    fn pull(&mut self, offset: usize) {
+        match self.is_trivia {
+            Some(is_trivia) => {
+                // Hidden inputs are set aside, along with the offset of the input that follows them
+                while self.buffer.len() <= offset {
+                    match self.iter.next() {
+                        Some(input) if is_trivia(&input.0) => {
+                            self.hidden.push((self.buffer.len(), input))
+                        }
+                        Some(input) => self.buffer.push(input),
+                        None => break,
+                    }
+                }
+            }
+            None => {
+                let additional = offset.saturating_sub(self.buffer.len()) + 1024;
+                #[allow(deprecated)]
+                (&mut &mut self.iter as &mut dyn StreamExtend<_>)
+                    .extend(&mut self.buffer, additional);
+            }
+        }
+    }
+
+    /// Pull every remaining input from the iterator.
+    fn pull_all(&mut self) {
+        loop {
+            let len = self.buffer.len();
+            self.pull(len);
+            if self.buffer.len() == len {
+                break;
+            }
+        }
+    }
+}
+
 impl<'a, I: Clone, S: Span + 'a> BoxStream<'a, I, S> {
     /// Create a new `Stream` from an iterator of nested tokens and a function that flattens them.
     ///
@@ -175,6 +312,14 @@ impl<'a, I: Clone, S: Span + 'a> BoxStream<'a, I, S> {
     }
 }
 
+// The number of recoveries among the records.
+fn count_recoveries(records: &[Record]) -> usize {
+    records
+        .iter()
+        .filter(|r| matches!(r.kind, RecordKind::Recovery))
+        .count()
+}
+
 impl<'a, I: Clone, S: Span> Stream<'a, I, S> {
     pub(crate) fn offset(&self) -> usize {
         self.offset
@@ -185,15 +330,205 @@ impl<'a, I: Clone, S: Span> Stream<'a, I, S> {
     }
     pub(crate) fn revert(&mut self, offset: usize) {
         self.offset = offset;
+        // Parsers that finished beyond the offset have been backtracked over
+        let len = self
+            .state
+            .records
+            .iter()
+            .rposition(|r| r.end <= offset)
+            .map_or(0, |idx| idx + 1);
+        self.truncate_records(len);
+    }
+
+    /// The number of parsers recorded so far (see [`ParseState::records`]).
+    pub(crate) fn record_checkpoint(&self) -> usize {
+        self.state.records.len()
+    }
+
+    /// Take the records made since the checkpoint, so that they can later be restored.
+    pub(crate) fn take_records(&mut self, checkpoint: usize) -> Vec<Record> {
+        let checkpoint = checkpoint.min(self.state.records.len());
+        let records = self.state.records.split_off(checkpoint);
+        self.state.recorded_recoveries -= count_recoveries(&records);
+        records
+    }
+
+    /// Restore records taken by [`Stream::take_records`].
+    pub(crate) fn restore_records(&mut self, records: Vec<Record>) {
+        self.state.recorded_recoveries += count_recoveries(&records);
+        self.state.records.extend(records);
+    }
+
+    /// Discard the records made since the checkpoint.
+    fn truncate_records(&mut self, checkpoint: usize) {
+        let checkpoint = checkpoint.min(self.state.records.len());
+        self.state.recorded_recoveries -= count_recoveries(&self.state.records[checkpoint..]);
+        self.state.records.truncate(checkpoint);
+    }
+
+    /// Record that a parser of the given kind succeeded, having started at `start`.
+    pub(crate) fn record(&mut self, kind: RecordKind, start: usize) {
+        if matches!(kind, RecordKind::Recovery) {
+            self.state.recorded_recoveries += 1;
+        }
+        self.state.records.push(Record {
+            kind,
+            start,
+            end: self.offset,
+        });
+    }
+
+    /// Begin invoking a memoized parser, so that its result can later be remembered with [`Stream::memoize`].
+    pub(crate) fn memo_checkpoint(&mut self) -> MemoCheckpoint {
+        let checkpoint = MemoCheckpoint {
+            start: self.offset,
+            reach: self.state.reach,
+            records: self.state.records.len(),
+            memos: self.state.memos.len(),
+            recoveries: self.state.recoveries,
+        };
+        self.state.reach = self.offset;
+        checkpoint
+    }
+
+    /// Finish invoking the memoized parser at `site`, remembering its output (see [`Memo::output`]) if it succeeded
+    /// without recovering from any errors.
+    pub(crate) fn memoize(
+        &mut self,
+        checkpoint: MemoCheckpoint,
+        site: Site,
+        output: Option<impl FnOnce() -> Arc<dyn Any + Send + Sync>>,
+    ) {
+        let reach = self.state.reach;
+        self.state.reach = reach.max(checkpoint.reach);
+        match output {
+            Some(output) if self.state.recoveries == checkpoint.recoveries => {
+                let records =
+                    self.state.records[checkpoint.records.min(self.state.records.len())..].to_vec();
+                // The results of the memoized parsers invoked by this one now belong to it
+                let nested = self.state.memos.split_off(checkpoint.memos);
+                let memo = Memo {
+                    site,
+                    start: checkpoint.start,
+                    end: self.offset,
+                    reach,
+                    output: output(),
+                    records,
+                    nested,
+                };
+                self.state.memos.push(Slot {
+                    memo: Arc::new(memo),
+                    offset_shift: 0,
+                    span_shift: 0,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Reuse the result of the memoized parser at `site` from a previous parse (see [`ParseConfig::reparse`]), if one
+    /// can be reused at the current offset, skipping the inputs that it parsed.
+    pub(crate) fn reuse<O, E>(&mut self, site: Site) -> Option<(O, Option<Located<I, E>>)>
+    where
+        O: Clone + Shift + 'static,
+        E: Error<I, Span = S> + Clone + Shift + 'static,
+    {
+        let slot = self.state.reusable.get(&(self.offset, site))?.clone();
+        let (output, alt) = slot.memo.output.downcast_ref::<(O, Option<(usize, E)>)>()?;
+        let (offset_shift, span_shift) = (slot.offset_shift, slot.span_shift);
+
+        let mut output = output.clone();
+        output.shift(span_shift);
+        let alt = alt.clone().map(|(at, mut error)| {
+            error.shift(span_shift);
+            Located::at(shift_offset(at, offset_shift), error)
+        });
+
+        let memo = &slot.memo;
+        self.offset = shift_offset(memo.end, offset_shift);
+        self.state.reach = self.state.reach.max(shift_offset(memo.reach, offset_shift));
+        self.state.recorded_recoveries += count_recoveries(&memo.records);
+        self.state
+            .records
+            .extend(memo.records.iter().map(|r| Record {
+                kind: r.kind,
+                start: shift_offset(r.start, offset_shift),
+                end: shift_offset(r.end, offset_shift),
+            }));
+        self.state.memos.push(slot);
+        Some((output, alt))
+    }
+
+    /// Find the runs of trivia at either end of the input parsed since `start`, given that the parser began at the
+    /// record checkpoint (see [`Stream::record_checkpoint`]).
+    pub(crate) fn trivia(&mut self, checkpoint: usize, start: usize) -> Trivia<I, S> {
+        let end = self.offset;
+        let records = self.state.records[checkpoint.min(self.state.records.len())..]
+            .iter()
+            .filter(|r| matches!(r.kind, RecordKind::Trivia))
+            .map(|r| (r.start, r.end))
+            .collect::<Vec<_>>();
+
+        // Records are made as parsers finish, so the first record found is the innermost
+        let mut leading = Vec::new();
+        let mut pos = start;
+        while let Some(&(a, b)) = records.iter().find(|(a, _)| *a == pos) {
+            leading.push((a, b));
+            pos = b;
+        }
+        let leading_end = pos;
+        let mut trailing = Vec::new();
+        let mut pos = end;
+        while let Some(&(a, b)) = records.iter().find(|(a, b)| *b == pos && *a >= leading_end) {
+            trailing.insert(0, (a, b));
+            pos = a;
+        }
+
+        let mut piece = |(a, b): (usize, usize)| {
+            let inputs = self.buffer[a..b].iter().map(|(i, _)| i.clone()).collect();
+            (inputs, self.span_between(a, b))
+        };
+        Trivia {
+            leading: leading.into_iter().map(&mut piece).collect(),
+            trailing: trailing.into_iter().map(&mut piece).collect(),
+        }
+    }
+
+    /// Pull every remaining input, returning all of the inputs, the hidden inputs (see [`Stream::hidden_before`]) and
+    /// the span at the end of the input.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn inputs(&mut self) -> (&[(I, S)], &[Hidden<I, S>], &S) {
+        self.pull_all();
+        (&self.buffer, &self.hidden, &self.eoi)
     }
 
     fn pull_until(&mut self, offset: usize) -> Option<&(I, S)> {
-        let additional = offset.saturating_sub(self.buffer.len()) + 1024;
-        #[allow(deprecated)]
-        (&mut &mut self.iter as &mut dyn StreamExtend<_>).extend(&mut self.buffer, additional);
+        self.pull(offset);
+        self.state.reach = self.state.reach.max(offset + 1);
         self.buffer.get(offset)
     }
 
+    /// The hidden inputs between the input at the given offset and the one that precedes it (see
+    /// [`Stream::from_iter_with_trivia`]).
+    pub(crate) fn hidden_before(&mut self, offset: usize) -> impl Iterator<Item = &(I, S)> + '_ {
+        // Pulling the input at the offset pulls every hidden input that precedes it
+        self.pull_until(offset);
+        let start = self
+            .hidden
+            .binary_search_by(|(at, _)| {
+                if *at < offset {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            })
+            .unwrap_or_else(|idx| idx);
+        self.hidden[start..]
+            .iter()
+            .take_while(move |(at, _)| *at == offset)
+            .map(|(_, input)| input)
+    }
+
     pub(crate) fn skip_if(&mut self, f: impl FnOnce(&I) -> bool) -> bool {
         match self.pull_until(self.offset).cloned() {
             Some((out, _)) if f(&out) => {
@@ -235,16 +570,168 @@ impl<'a, I: Clone, S: Span> Stream<'a, I, S> {
         S::new(self.eoi.context(), start..end)
     }
 
+    /// Handle an iteration of the repeating parser at `location` that succeeded without consuming any input, as
+    /// configured by [`ParseConfig::on_stalled_loop`].
+    pub(crate) fn stalled_loop<E: Error<I, Span = S>>(
+        &mut self,
+        location: Location<'static>,
+    ) -> Option<Located<I, E>> {
+        match self.state.config.stalled_loop() {
+            StalledLoop::Stop => None,
+            StalledLoop::Error => {
+                let span = self.empty_span();
+                Some(Located::at(self.offset, E::stalled_loop(span, location)))
+            }
+            StalledLoop::Panic => panic!(
+                "Repeated parser at {} succeeded without consuming any input: continuing would never terminate. \
+                This is likely indicative of a parser bug, such as repeating a parser that can match nothing.",
+                location
+            ),
+        }
+    }
+
+    /// Handle the recursive parser declared at `location` being re-entered without consuming any input, as configured
+    /// by [`ParseConfig::on_left_recursion`].
+    pub(crate) fn left_recursion<E: Error<I, Span = S>>(
+        &mut self,
+        location: Location<'static>,
+    ) -> Located<I, E> {
+        match self.state.config.left_recursion() {
+            LeftRecursion::Error => {
+                let span = self.empty_span();
+                Located::at(self.offset, E::left_recursion(span, location))
+            }
+            LeftRecursion::Panic => panic!(
+                "Recursive parser declared at {} was re-entered without consuming any input: it is left-recursive, so \
+                parsing would never terminate. Consider rewriting the rule to parse its left operand first, such as \
+                with `Parser::repeated` and `Parser::foldl`.",
+                location
+            ),
+        }
+    }
+
+    /// Take (a copy of) the first `n` inputs, along with an empty span at the position following them.
+    pub(crate) fn prefix(&mut self, n: usize) -> (Vec<(I, S)>, S) {
+        let span = self.empty_span_at(n);
+        (self.buffer.iter().take(n).cloned().collect(), span)
+    }
+
+    /// Returns `true` if the given offset is at (or beyond) the end of the input.
+    pub(crate) fn is_eoi(&mut self, offset: usize) -> bool {
+        self.pull_until(offset).is_none()
+    }
+
+    /// An empty span at the start of the next input.
+    fn empty_span(&mut self) -> S {
+        self.empty_span_at(self.offset)
+    }
+
+    /// An empty span at the start of the input at the given offset.
+    pub(crate) fn empty_span_at(&mut self, offset: usize) -> S {
+        let at = self
+            .pull_until(offset)
+            .map(|(_, s)| s.start())
+            .unwrap_or_else(|| self.eoi.start());
+        S::new(self.eoi.context(), at.clone()..at)
+    }
+
+    /// Take a step of the parse, failing if the parse has run out of fuel (see [`ParseConfig::fuel`]), has been
+    /// cancelled (see [`ParseConfig::cancel_on`]) or has already been abandoned.
+    pub(crate) fn step<E: Error<I, Span = S>>(&mut self) -> Result<(), Located<I, E>> {
+        if let Some((reason, _)) = self.state.abandoned {
+            return Err(self.abandon(reason));
+        }
+        if self.state.steps & (CHECK_INTERVAL - 1) == 0 {
+            if let Some(progress) = &self.state.config.progress {
+                (progress.0)(self.offset);
+            }
+            if matches!(&self.state.config.cancel, Some(flag) if flag.load(AtomicOrdering::Relaxed))
+            {
+                return Err(self.abandon(Abandon::Cancelled));
+            }
+        }
+        if let Some(fuel) = self.state.config.fuel {
+            if self.state.steps >= fuel {
+                return Err(self.abandon(Abandon::Limit(Limit::Fuel(fuel))));
+            }
+        }
+        self.state.steps += 1;
+        Ok(())
+    }
+
+    /// Abandon the parse for the given reason, so that every subsequent step fails.
+    pub(crate) fn abandon<E: Error<I, Span = S>>(&mut self, reason: Abandon) -> Located<I, E> {
+        if self.state.abandoned.is_none() {
+            self.state.abandoned = Some((reason, self.offset));
+        }
+        let span = self.empty_span();
+        Located::at(self.offset, reason.error(span))
+    }
+
+    /// Returns `true` if the parse is still permitted to recover from errors.
+    pub(crate) fn can_recover(&self) -> bool {
+        if self.state.abandoned.is_some() {
+            return false;
+        }
+        match self.state.config.max_errors {
+            // Only recoveries that haven't been backtracked over count
+            Some(max) => self.state.recorded_recoveries < max,
+            None => true,
+        }
+    }
+
+    /// Record that the input since `start` was recovered from, marking the errors that caused the recovery as such.
+    pub(crate) fn recovered<E: Error<I>>(
+        &mut self,
+        start: usize,
+        errors: &mut [Located<I, E>],
+        strategy: &'static str,
+        location: Location<'static>,
+    ) {
+        let region = Region {
+            id: self.state.recoveries,
+            end: self.offset,
+        };
+        self.state.recoveries += 1;
+        self.record(RecordKind::Recovery, start);
+        if self.state.config.log_recoveries {
+            self.state.log.push(RecoveryRecord {
+                id: region.id,
+                strategy,
+                location,
+                start,
+                end: self.offset,
+            });
+        }
+        for error in errors
+            .iter_mut()
+            .filter(|e| e.region.is_none() && e.at >= start)
+        {
+            error.region = Some(region);
+        }
+    }
+
+    /// Get the span covering the inputs between the two offsets.
+    pub(crate) fn span_between(&mut self, start: usize, end: usize) -> S {
+        let offset = self.offset;
+        self.offset = end;
+        let span = self.span_since(start);
+        self.offset = offset;
+        span
+    }
+
     pub(crate) fn attempt<R, F: FnOnce(&mut Self) -> (bool, R)>(&mut self, f: F) -> R {
         let old_offset = self.offset;
+        let checkpoint = self.record_checkpoint();
         let (commit, out) = f(self);
         if !commit {
             self.offset = old_offset;
+            self.truncate_records(checkpoint);
         }
         out
     }
 
-    pub(crate) fn try_parse<O, E, F: FnOnce(&mut Self) -> PResult<I, O, E>>(
+    pub(crate) fn try_parse<O, E: Error<I>, F: FnOnce(&mut Self) -> PResult<I, O, E>>(
         &mut self,
         f: F,
     ) -> PResult<I, O, E> {
@@ -266,6 +753,7 @@ impl<'a> From<&'a str>
             len..len,
             Box::new(s.chars().enumerate().map(|(i, c)| (c, i..i + 1))),
         )
+        .describe_tokens()
     }
 }
 
@@ -280,6 +768,7 @@ impl<'a> From<String>
             chars.len()..chars.len(),
             Box::new(chars.into_iter().enumerate().map(|(i, c)| (c, i..i + 1))),
         )
+        .describe_tokens()
     }
 }
 
@@ -340,3 +829,31 @@ impl<'a, T: Clone, const N: usize> From<&'a [T; N]>
 //         Self::from_iter(Default::default(), Box::new(s.iter().cloned()))
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Simple, primitive::trivia_before};
+
+    #[test]
+    fn fetch_tokens_hides_trivia() {
+        let tokens = vec![('#', 0..1), ('a', 1..2), ('#', 2..3), ('b', 3..4)];
+        let mut stream = Stream::from_iter_with_trivia(4..4, tokens.into_iter(), |c| *c == '#');
+
+        assert_eq!(
+            stream.fetch_tokens().collect::<Vec<_>>(),
+            vec![('a', 1..2), ('b', 3..4)],
+        );
+
+        // The hidden tokens are still available to parsers after fetching
+        let parser = trivia_before::<_, Simple<char>>()
+            .then_ignore(just('a'))
+            .then(trivia_before())
+            .then_ignore(just('b'))
+            .then_ignore(end());
+        assert_eq!(
+            parser.parse(stream),
+            Ok((vec![('#', 0..1)], vec![('#', 2..3)])),
+        );
+    }
+}
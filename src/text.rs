@@ -10,6 +10,7 @@
 //! parsing of text.
 
 use super::*;
+use crate::grammar::Described;
 use core::iter::FromIterator;
 
 /// The type of a parser that accepts (and ignores) any number of whitespace characters.
@@ -48,6 +49,17 @@ impl<C: Character, O, A: Parser<C, O, Error = E>, E: Error<C>> Parser<C, O> for
         }
     }
 
+    fn describe(&self, describer: &mut Describer<C, E>) -> Node<C> {
+        Node::new(
+            "padded",
+            Kind::Seq(vec![
+                describe_whitespace(),
+                self.0.describe(describer),
+                describe_whitespace(),
+            ]),
+        )
+    }
+
     #[inline]
     fn parse_inner_verbose(&self, d: &mut Verbose, s: &mut StreamOf<C, E>) -> PResult<C, O, E> {
         #[allow(deprecated)]
@@ -60,6 +72,21 @@ impl<C: Character, O, A: Parser<C, O, Error = E>, E: Error<C>> Parser<C, O> for
     }
 }
 
+// Any number of whitespace characters, as skipped by `Padded`.
+fn describe_whitespace<C>() -> Node<C> {
+    Node::new(
+        "whitespace",
+        Kind::Repeat {
+            item: Box::new(Node::new("whitespace", Kind::Terminal("whitespace"))),
+            separator: None,
+            at_least: 0,
+            at_most: None,
+            allow_leading: false,
+            allow_trailing: false,
+        },
+    )
+}
+
 mod private {
     pub trait Sealed {}
 
@@ -98,6 +125,9 @@ pub trait Character: private::Sealed + Copy + PartialEq {
 
     /// Returns this character as a [`char`].
     fn to_char(&self) -> char;
+
+    /// Collect the characters of the given string into a [`Vec`].
+    fn str_to_vec(s: &Self::Str) -> Vec<Self>;
 }
 
 impl Character for u8 {
@@ -122,6 +152,9 @@ impl Character for u8 {
     fn to_char(&self) -> char {
         *self as char
     }
+    fn str_to_vec(s: &Self::Str) -> Vec<Self> {
+        s.to_vec()
+    }
 }
 
 impl Character for char {
@@ -146,6 +179,9 @@ impl Character for char {
     fn to_char(&self) -> char {
         *self
     }
+    fn str_to_vec(s: &Self::Str) -> Vec<Self> {
+        s.chars().collect()
+    }
 }
 
 /// A trait containing text-specific functionality that extends the [`Parser`] trait.
@@ -232,7 +268,7 @@ pub fn whitespace<'a, C: Character + 'a, E: Error<C> + 'a>(
 #[must_use]
 pub fn newline<'a, C: Character + 'a, E: Error<C> + 'a>(
 ) -> impl Parser<C, (), Error = E> + Copy + Clone + 'a {
-    just(C::from_ascii(b'\r'))
+    let newline = just(C::from_ascii(b'\r'))
         .or_not()
         .ignore_then(just(C::from_ascii(b'\n')))
         .or(filter(|c: &C| {
@@ -246,7 +282,8 @@ pub fn newline<'a, C: Character + 'a, E: Error<C> + 'a>(
             ]
             .contains(&c.to_char())
         }))
-        .ignored()
+        .ignored();
+    Described(newline, || Node::new("newline", Kind::Terminal("newline")))
 }
 
 /// A parser that accepts one or more ASCII digits.
@@ -274,10 +311,11 @@ pub fn newline<'a, C: Character + 'a, E: Error<C> + 'a>(
 pub fn digits<C: Character, E: Error<C>>(
     radix: u32,
 ) -> impl Parser<C, C::Collection, Error = E> + Copy + Clone {
-    filter(move |c: &C| c.is_digit(radix))
+    let digits = filter(move |c: &C| c.is_digit(radix))
         .repeated()
         .at_least(1)
-        .collect()
+        .collect();
+    Described(digits, || Node::new("digits", Kind::Terminal("digits")))
 }
 
 /// A parser that accepts a non-negative integer.
@@ -315,11 +353,12 @@ pub fn digits<C: Character, E: Error<C>>(
 pub fn int<C: Character, E: Error<C>>(
     radix: u32,
 ) -> impl Parser<C, C::Collection, Error = E> + Copy + Clone {
-    filter(move |c: &C| c.is_digit(radix) && c != &C::digit_zero())
+    let int = filter(move |c: &C| c.is_digit(radix) && c != &C::digit_zero())
         .map(Some)
         .chain::<C, Vec<_>, _>(filter(move |c: &C| c.is_digit(radix)).repeated())
         .collect()
-        .or(just(C::digit_zero()).map(|c| core::iter::once(c).collect()))
+        .or(just(C::digit_zero()).map(|c| core::iter::once(c).collect()));
+    Described(int, || Node::new("int", Kind::Terminal("int")))
 }
 
 /// A parser that accepts a C-style identifier.
@@ -332,12 +371,13 @@ pub fn int<C: Character, E: Error<C>>(
 #[must_use]
 pub fn ident<C: Character, E: Error<C>>() -> impl Parser<C, C::Collection, Error = E> + Copy + Clone
 {
-    filter(|c: &C| c.to_char().is_ascii_alphabetic() || c.to_char() == '_')
+    let ident = filter(|c: &C| c.to_char().is_ascii_alphabetic() || c.to_char() == '_')
         .map(Some)
         .chain::<C, Vec<_>, _>(
             filter(|c: &C| c.to_char().is_ascii_alphanumeric() || c.to_char() == '_').repeated(),
         )
-        .collect()
+        .collect();
+    Described(ident, || Node::new("ident", Kind::Terminal("ident")))
 }
 
 /// Like [`ident`], but only accepts an exact identifier while ignoring trailing identifier characters.
@@ -361,13 +401,20 @@ pub fn ident<C: Character, E: Error<C>>() -> impl Parser<C, C::Collection, Error
 pub fn keyword<'a, C: Character + 'a, S: AsRef<C::Str> + 'a + Clone, E: Error<C> + 'a>(
     keyword: S,
 ) -> impl Parser<C, (), Error = E> + Clone + 'a {
+    let chars = C::str_to_vec(keyword.as_ref());
     // TODO: use .filter(...), improve error messages
-    ident().try_map(move |s: C::Collection, span| {
+    let keyword = ident().try_map(move |s: C::Collection, span| {
         if s.as_ref() == keyword.as_ref() {
             Ok(())
         } else {
             Err(E::expected_input_found(span, None, None))
         }
+    });
+    Described(keyword, move || {
+        Node::new(
+            "keyword",
+            Kind::TryMap(Box::new(Node::new("ident", Kind::Just(chars.clone())))),
+        )
     })
 }
 
@@ -0,0 +1,107 @@
+//! Trivia, such as comments and whitespace, that is not part of the output of a parser but that you may still want to
+//! keep hold of.
+//!
+//! *“Time is an illusion. Lunchtime doubly so.”*
+//!
+//! Parsers marked with [`Parser::record_trivia`] record the inputs that they consume. [`Parser::map_with_trivia`]
+//! then hands the trivia found at either end of the input parsed by a parser to a function, allowing comments (such
+//! as doc comments) to be attached to the AST nodes that they belong to.
+
+use super::*;
+
+/// The trivia at either end of the input parsed by a parser. See [`Parser::map_with_trivia`].
+///
+/// Each piece of trivia is the input consumed by a single invocation of a parser marked with
+/// [`Parser::record_trivia`], given as the inputs along with their span. When trivia parsers are nested within one
+/// another, the innermost pieces are given.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trivia<I, S> {
+    pub(crate) leading: Vec<(Vec<I>, S)>,
+    pub(crate) trailing: Vec<(Vec<I>, S)>,
+}
+
+impl<I, S> Trivia<I, S> {
+    /// The run of trivia at the start of the input, in order.
+    pub fn leading(&self) -> &[(Vec<I>, S)] {
+        &self.leading
+    }
+
+    /// The run of trivia at the end of the input, in order. Trivia at the start of the input is never also included
+    /// here, even if it extends to the end.
+    pub fn trailing(&self) -> &[(Vec<I>, S)] {
+        &self.trailing
+    }
+
+    /// Returns `true` if there is no trivia at either end of the input.
+    pub fn is_empty(&self) -> bool {
+        self.leading.is_empty() && self.trailing.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cst::tests::render, error::Simple};
+
+    #[test]
+    fn trivia() {
+        fn pieces(trivia: &[(Vec<char>, Range<usize>)]) -> Vec<(String, Range<usize>)> {
+            trivia
+                .iter()
+                .map(|(c, span)| (c.iter().collect(), span.clone()))
+                .collect()
+        }
+
+        let comment = just::<_, _, Simple<char>>('#')
+            .then(take_until(just('#')))
+            .ignored()
+            .record_trivia();
+        let space = just(' ').repeated().at_least(1).ignored().record_trivia();
+        let trivia = comment.or(space).repeated();
+
+        // The first alternative parses trivia before failing, so its trivia must be discarded
+        let atom = trivia
+            .clone()
+            .ignore_then(just('x'))
+            .then_ignore(just('!'))
+            .or(trivia.clone().ignore_then(just('x')).then_ignore(trivia))
+            .map_with_trivia(|_, trivia| (pieces(trivia.leading()), pieces(trivia.trailing())));
+
+        assert_eq!(
+            atom.parse("#a# x #b#"),
+            Ok((
+                vec![("#a#".to_string(), 0..3), (" ".to_string(), 3..4)],
+                vec![(" ".to_string(), 5..6), ("#b#".to_string(), 6..9)],
+            )),
+        );
+        assert_eq!(atom.parse("x"), Ok((vec![], vec![])));
+    }
+
+    #[test]
+    fn hidden_trivia() {
+        let src = " a  b ";
+        let stream = || {
+            Stream::from_iter_with_trivia(
+                6..6,
+                src.chars().enumerate().map(|(i, c)| (c, i..i + 1)),
+                |c: &char| c.is_whitespace(),
+            )
+        };
+
+        let letter = filter::<_, _, Simple<char>>(char::is_ascii_alphabetic).labelled("letter");
+        let parser = letter
+            .repeated()
+            .labelled("word")
+            .then(primitive::trivia_before())
+            .then_ignore(end());
+
+        let output = parser.parse_recovery_with(stream(), ParseConfig::new().cst());
+        assert_eq!(output.output(), Some(&(vec!['a', 'b'], vec![(' ', 5..6)])),);
+
+        // Hidden inputs between nodes belong to the node that encloses them
+        let cst = output.cst().unwrap();
+        assert_eq!(cst.to_string(), src);
+        assert_eq!(render(cst), " word[letter[a]  letter[b]] ");
+        assert_eq!(cst.nodes().next().unwrap().span(), &(1..5));
+    }
+}